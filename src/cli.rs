@@ -7,7 +7,9 @@ pub enum ResponseValidationChoice {
     /// No validation.
     #[default]
     None,
-    /// Validates the result is any status code less than 400.
+    /// Validates the status code is the documented success status, e.g. `201`, or within the
+    /// documented success statuses if there are several. Validates it's less than 400 if no
+    /// success status is documented.
     NonErrorCode,
     /// Validates the structure and types of the response body.
     /// Note: This tool will not produce response validation for union types (nullable, oneOf, not
@@ -29,6 +31,33 @@ pub enum QueryParamChoice {
     All,
 }
 
+#[derive(ValueEnum, Clone, Default)]
+pub enum BodyFieldChoice {
+    /// Only the required properties, the smallest valid request body.
+    Required,
+    /// Sets a value for all properties
+    #[default]
+    All,
+}
+
+#[derive(ValueEnum, Clone, Default)]
+pub enum NullValueChoice {
+    /// Nullable properties get a value of their non-null type.
+    #[default]
+    NonNull,
+    /// An extra entry with every nullable property of a JSON request body set to null.
+    Extra,
+}
+
+#[derive(ValueEnum, Clone, Default)]
+pub enum DataChoice {
+    /// The same sample values every time.
+    #[default]
+    Fixed,
+    /// Random sample values. Pass a `seed` to generate the same values again.
+    Random,
+}
+
 #[derive(ValueEnum, Clone, Default)]
 pub enum VariablesUpdateStrategy {
     /// Overwrites the entire variables file with new variables.
@@ -77,6 +106,28 @@ pub enum CliContentType {
     Text,
     #[default]
     Json,
+    MultipartFormData,
+    FormUrlEncoded,
+    Xml,
+    Binary,
+}
+
+#[derive(ValueEnum, Clone, Default)]
+pub enum RequestMediaTypeChoice {
+    /// One entry using the preferred media type of the request body.
+    #[default]
+    Preferred,
+    /// One entry for every media type of the request body supported by this tool.
+    All,
+}
+
+#[derive(ValueEnum, Clone, Default)]
+pub enum UnionVariantChoice {
+    /// One entry using the first variant of a oneOf or anyOf request body.
+    #[default]
+    First,
+    /// One entry for every variant of a oneOf or anyOf request body.
+    All,
 }
 
 #[derive(ValueEnum, Clone, Default)]
@@ -123,12 +174,43 @@ pub struct Cli {
     /// This option indicates how the response should be validated.
     #[arg(short = 'n', long, default_value_t = ResponseValidationChoice::default(), value_enum)]
     pub validation: ResponseValidationChoice,
+    /// The documented response to validate, e.g. `201` or `404`, defaults to the success
+    /// response. `all` generates an entry for every documented status code. Requests are arranged
+    /// to trigger the status, with a nonexistent path param for `404` and `410`, a JSON body
+    /// without its required properties for `400` and `422`, or without the `header-vars` for
+    /// `401`. Other error statuses get no entry, and neither do these if there's no path param,
+    /// such a body or `header-vars` to arrange. Undocumented responses aren't validated and the
+    /// request isn't arranged for them. Has no effect without `validation`.
+    #[arg(long, value_parser = parse_response_code)]
+    pub response_code: Option<String>,
     /// Variables will be added to the hurl variables file and the header of each request. Format: `HEADER_KEY=HEADER_VALUE`.
     #[arg(short = 'r', long, value_parser = parse_key_val::<String, String>)]
     pub header_vars: Vec<(String, String)>,
     /// Choose whether, and how to, pass query params.
     #[arg(short = 'q', long, default_value_t = QueryParamChoice::default(), value_enum)]
     pub query_params: QueryParamChoice,
+    /// Choose whether request bodies include only the required properties or all of them.
+    #[arg(long, default_value_t = BodyFieldChoice::default(), value_enum)]
+    pub body_fields: BodyFieldChoice,
+    /// Generate an entry for every enum value of the JSON request body property with this name,
+    /// e.g. to cover every state of a status field.
+    #[arg(long)]
+    pub enum_property: Option<String>,
+    /// Choose whether nullable properties of request bodies only get non-null values or an extra
+    /// entry sets them to null, to test null handling on purpose.
+    #[arg(long, default_value_t = NullValueChoice::default(), value_enum)]
+    pub null_values: NullValueChoice,
+    /// How sample values are generated for request bodies, path params and query params.
+    #[arg(long, default_value_t = DataChoice::default(), value_enum)]
+    pub data: DataChoice,
+    /// The seed of the random sample values. Has no effect unless `--data random` is given.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Set this to true to let hurl generate values that have to be unique, e.g. `uuid` and
+    /// `email` formats and idempotency keys, with `{{newUuid}}`. The files can then be run
+    /// repeatedly against the same environment.
+    #[arg(long, default_value_t = false)]
+    pub dynamic_values: bool,
     /// Pass this argument to select how the path params will be represented in hurl.
     #[arg(short = 'p', long, default_value_t = PathParamChoice::default(), value_enum)]
     pub path_params: PathParamChoice,
@@ -151,6 +233,29 @@ pub struct Cli {
     /// tool. If no valid content type is found the tool will use an empty request body.
     #[arg(long, default_value_t = CliContentType::default(), value_enum)]
     pub content_type: CliContentType,
+    /// Preferred media types of the request and response bodies in order of preference, e.g.
+    /// `--media-type 'application/merge-patch+json,application/*+json'`. `*` matches any
+    /// characters. Takes precedence over `content-type`, which is used when an operation doesn't
+    /// offer any of the media types.
+    #[arg(long, value_delimiter = ',')]
+    pub media_type: Vec<String>,
+    /// Choose whether to generate an entry for the preferred media type of the request body or
+    /// for all of them.
+    #[arg(long, default_value_t = RequestMediaTypeChoice::default(), value_enum)]
+    pub request_media_types: RequestMediaTypeChoice,
+    /// Set this to true to leave the `Content-Type` and `Accept` headers out of the requests.
+    /// The `Content-Type` of a file sent as the request body is always set.
+    #[arg(long, default_value_t = false)]
+    pub no_media_type_headers: bool,
+    /// Choose whether to generate an entry for the first variant of a JSON request body with a
+    /// oneOf or anyOf schema or for all of them.
+    #[arg(long, default_value_t = UnionVariantChoice::default(), value_enum)]
+    pub union_variants: UnionVariantChoice,
+    /// How deeply schemas can be nested in generated request bodies and response validation,
+    /// defaults to 10. Recursive schemas are cut off when they reference themselves. Optional
+    /// properties cut off are left out, required ones are set to an empty array or null.
+    #[arg(long)]
+    pub max_depth: Option<usize>,
     #[arg(short = 'l', long, default_value_t = LogLevel::default(), value_enum)]
     pub log_level: LogLevel,
     /// Set this to true to silence all logging.
@@ -167,6 +272,21 @@ pub struct Cli {
     pub version: bool,
 }
 
+/// Parse `all` or the key of a response, a status code like `404`, a range like `4XX` or `default`
+fn parse_response_code(s: &str) -> Result<String, String> {
+    let is_status = s.len() == 3
+        && s.is_ascii()
+        && s[..1].chars().all(|c| ('1'..='5').contains(&c))
+        && (s[1..].chars().all(|c| c.is_ascii_digit()) || s[1..].eq_ignore_ascii_case("XX"));
+
+    match is_status || s == "default" || s == "all" {
+        true => Ok(s.to_string()),
+        false => Err(format!(
+            "invalid response code `{s}`: expected a status code like `404`, a range like `4XX`, `default` or `all`"
+        )),
+    }
+}
+
 /// Parse a single key-value pair
 fn parse_key_val<T, U>(s: &str) -> Result<(T, U), Box<dyn Error + Send + Sync + 'static>>
 where
@@ -180,3 +300,17 @@ where
         .ok_or_else(|| format!("invalid KEY=value: no `=` found in `{s}`"))?;
     Ok((s[..pos].parse()?, s[pos + 1..].parse()?))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse_response_code;
+
+    #[test]
+    fn parse_response_code_accepts_status_codes_and_ranges() {
+        assert_eq!(Ok("404".to_string()), parse_response_code("404"));
+        assert_eq!(Ok("4XX".to_string()), parse_response_code("4XX"));
+        assert_eq!(Ok("default".to_string()), parse_response_code("default"));
+        assert!(parse_response_code("600").is_err());
+        assert!(parse_response_code("é1").is_err());
+    }
+}
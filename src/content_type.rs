@@ -1,13 +1,25 @@
+use std::collections::BTreeMap;
+
 use crate::cli::CliContentType;
 
 const PLAIN_TEXT: &str = "text";
 const JSON: &str = "json";
+const MULTIPART_FORM_DATA: &str = "multipart/form-data";
+const FORM_URL_ENCODED: &str = "application/x-www-form-urlencoded";
+const XML: &str = "xml";
+const OCTET_STREAM: &str = "application/octet-stream";
+const IMAGE: &str = "image/";
+const PDF: &str = "application/pdf";
 
 #[derive(Clone, Default)]
 pub enum ContentType {
     Text,
     #[default]
     Json,
+    MultipartFormData,
+    FormUrlEncoded,
+    Xml,
+    Binary,
 }
 
 impl From<CliContentType> for ContentType {
@@ -15,6 +27,10 @@ impl From<CliContentType> for ContentType {
         match value {
             CliContentType::Text => Self::Text,
             CliContentType::Json => Self::Json,
+            CliContentType::MultipartFormData => Self::MultipartFormData,
+            CliContentType::FormUrlEncoded => Self::FormUrlEncoded,
+            CliContentType::Xml => Self::Xml,
+            CliContentType::Binary => Self::Binary,
         }
     }
 }
@@ -24,6 +40,10 @@ impl ContentType {
         match self {
             ContentType::Text => str.contains(PLAIN_TEXT),
             ContentType::Json => str.contains(JSON),
+            ContentType::MultipartFormData => str.contains(MULTIPART_FORM_DATA),
+            ContentType::FormUrlEncoded => str.contains(FORM_URL_ENCODED),
+            ContentType::Xml => str.contains(XML),
+            ContentType::Binary => is_binary(str),
         }
     }
 
@@ -38,15 +58,38 @@ impl ContentType {
         match self {
             ContentType::Text => PLAIN_TEXT,
             ContentType::Json => JSON,
+            ContentType::MultipartFormData => MULTIPART_FORM_DATA,
+            ContentType::FormUrlEncoded => FORM_URL_ENCODED,
+            ContentType::Xml => XML,
+            ContentType::Binary => OCTET_STREAM,
         }
     }
 
     pub fn supported_types() -> Vec<String> {
-        vec![PLAIN_TEXT.to_string(), JSON.to_string()]
+        vec![
+            PLAIN_TEXT.to_string(),
+            JSON.to_string(),
+            MULTIPART_FORM_DATA.to_string(),
+            FORM_URL_ENCODED.to_string(),
+            XML.to_string(),
+            OCTET_STREAM.to_string(),
+            IMAGE.to_string(),
+            PDF.to_string(),
+        ]
     }
 
     pub fn from_string(content_type: &String) -> Result<Self, String> {
-        if content_type.contains(PLAIN_TEXT) {
+        // Checked first so images like `image/svg+xml` are sent as files
+        if is_binary(content_type) {
+            Ok(Self::Binary)
+        } else if content_type.contains(MULTIPART_FORM_DATA) {
+            Ok(Self::MultipartFormData)
+        } else if content_type.contains(FORM_URL_ENCODED) {
+            Ok(Self::FormUrlEncoded)
+        } else if content_type.contains(XML) {
+            // Checked before plain text so `text/xml` is treated as XML
+            Ok(Self::Xml)
+        } else if content_type.contains(PLAIN_TEXT) {
             Ok(Self::Text)
         } else if content_type.contains(JSON) {
             Ok(Self::Json)
@@ -55,3 +98,116 @@ impl ContentType {
         }
     }
 }
+
+fn is_binary(content_type: &str) -> bool {
+    content_type.contains(OCTET_STREAM)
+        || content_type.contains(IMAGE)
+        || content_type.contains(PDF)
+}
+
+/// Finds the content of the first media type in the preference list that the operation offers
+/// and this tool supports. Preferences can contain wildcards, e.g. `application/*+json`.
+pub fn preferred_content<'a, T>(
+    content: &'a BTreeMap<String, T>,
+    media_types: &[String],
+) -> Option<(&'a String, &'a T)> {
+    media_types.iter().find_map(|pattern| {
+        content
+            .iter()
+            .find(|c| media_type_matches(pattern, c.0) && ContentType::from_string(c.0).is_ok())
+    })
+}
+
+/// Matches a media type against a pattern where `*` matches any run of characters. Parameters
+/// like `; charset=utf-8` are ignored and the comparison is case insensitive.
+fn media_type_matches(pattern: &str, media_type: &str) -> bool {
+    let essence = |mt: &str| mt.split(';').next().unwrap_or("").trim().to_lowercase();
+    let pattern = essence(pattern);
+    let media_type = essence(media_type);
+
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let mut rest = match media_type.strip_prefix(first) {
+        Some(rest) => rest,
+        None => return false,
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    let last = match parts.last() {
+        Some(last) => *last,
+        // No wildcards, the whole media type has to match
+        None => return rest.is_empty(),
+    };
+
+    for part in &parts[..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{media_type_matches, preferred_content};
+
+    #[test]
+    fn media_type_matches_exact_and_wildcard_patterns() {
+        assert!(media_type_matches("application/json", "application/json"));
+        assert!(media_type_matches(
+            "application/json",
+            "Application/JSON; charset=utf-8"
+        ));
+        assert!(!media_type_matches(
+            "application/json",
+            "application/vnd.api+json"
+        ));
+        assert!(media_type_matches(
+            "application/*+json",
+            "application/vnd.api+json"
+        ));
+        assert!(media_type_matches(
+            "application/*+json",
+            "application/merge-patch+json"
+        ));
+        assert!(!media_type_matches(
+            "application/*+json",
+            "application/json"
+        ));
+        assert!(media_type_matches("*/*", "text/plain"));
+        assert!(!media_type_matches("image/*", "application/octet-stream"));
+    }
+
+    #[test]
+    fn preferred_content_follows_the_order_of_preferences() {
+        let content = BTreeMap::from([
+            ("application/json".to_string(), 1),
+            ("application/merge-patch+json".to_string(), 2),
+            ("application/vnd.api+json".to_string(), 3),
+        ]);
+
+        let preferences = vec![
+            "application/vnd.*".to_string(),
+            "application/json".to_string(),
+        ];
+        assert_eq!(
+            Some((&"application/vnd.api+json".to_string(), &3)),
+            preferred_content(&content, &preferences)
+        );
+
+        let preferences = vec!["text/*".to_string(), "application/*+json".to_string()];
+        assert_eq!(
+            Some((&"application/merge-patch+json".to_string(), &2)),
+            preferred_content(&content, &preferences)
+        );
+
+        assert_eq!(
+            None,
+            preferred_content(&content, &["text/plain".to_string()])
+        );
+    }
+}
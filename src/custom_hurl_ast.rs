@@ -1,4 +1,8 @@
-use hurl_core::ast::{LineTerminator, Pos, SourceInfo, Whitespace};
+use hurl_core::ast::{
+    KeyValue, LineTerminator, Pos, SourceInfo, Template, TemplateElement, Whitespace,
+};
+
+use crate::sample_values::values::NEW_UUID;
 
 pub fn empty_source_info() -> SourceInfo {
     SourceInfo {
@@ -31,3 +35,154 @@ pub fn empty_space() -> Whitespace {
         source_info: empty_source_info(),
     }
 }
+
+pub fn unquoted_template(value: String) -> Template {
+    Template {
+        delimiter: None,
+        elements: vec![TemplateElement::String {
+            value: "".to_string(),
+            encoded: value,
+        }],
+        source_info: empty_source_info(),
+    }
+}
+
+/// A template of the text as is. Characters hurl would otherwise read as a comment, a template
+/// or an escape sequence are escaped, `{{newUuid}}` is the only template kept.
+pub fn text_template(value: String) -> Template {
+    Template {
+        delimiter: None,
+        elements: vec![TemplateElement::String {
+            encoded: escape_text(&value),
+            value,
+        }],
+        source_info: empty_source_info(),
+    }
+}
+
+/// A template of the key as is, escaping the characters a key can't contain.
+pub fn key_template(key: String) -> Template {
+    Template {
+        delimiter: None,
+        elements: vec![TemplateElement::String {
+            encoded: escape_key(&key),
+            value: key,
+        }],
+        source_info: empty_source_info(),
+    }
+}
+
+pub fn key_value(key: String, value: String) -> KeyValue {
+    KeyValue {
+        line_terminators: vec![],
+        space0: empty_space(),
+        key: key_template(key),
+        space1: empty_space(),
+        space2: Whitespace {
+            value: " ".to_string(),
+            source_info: empty_source_info(),
+        },
+        value: text_template(value),
+        line_terminator0: newline(),
+    }
+}
+
+/// Escapes the text of a value, see https://hurl.dev/docs/grammar.html#value-string. The
+/// first `{` of `{{` is escaped so it doesn't start a template, spaces at the start or the end
+/// are escaped so they aren't trimmed.
+fn escape_text(text: &str) -> String {
+    let content_start = text.len() - text.trim_start_matches(' ').len();
+    let content_end = text.trim_end_matches(' ').len();
+    let mut escaped = String::new();
+    let mut i = 0;
+
+    while let Some(c) = text[i..].chars().next() {
+        if text[i..].starts_with(NEW_UUID) {
+            escaped.push_str(NEW_UUID);
+            i += NEW_UUID.len();
+            continue;
+        }
+
+        let next = text[i + c.len_utf8()..].chars().next();
+        match c {
+            ' ' if i < content_start || i >= content_end => escaped.push_str(&unicode_escape(c)),
+            '{' if next == Some('{') => escaped.push_str(&unicode_escape(c)),
+            c => escaped.push_str(&escape_char(c).unwrap_or(c.to_string())),
+        }
+        i += c.len_utf8();
+    }
+
+    escaped
+}
+
+/// Escapes the characters of a key, see https://hurl.dev/docs/grammar.html#key-string.
+fn escape_key(key: &str) -> String {
+    key.chars()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '[' if i == 0 => unicode_escape(c),
+            c if c.is_alphanumeric() || "_-.[]@$".contains(c) => c.to_string(),
+            ':' => "\\:".to_string(),
+            c => escape_char(c).unwrap_or(unicode_escape(c)),
+        })
+        .collect()
+}
+
+fn escape_char(c: char) -> Option<String> {
+    let escaped = match c {
+        '#' => "\\#",
+        '\\' => "\\\\",
+        '\n' => "\\n",
+        '\r' => "\\r",
+        '\t' => "\\t",
+        '\x08' => "\\b",
+        '\x0c' => "\\f",
+        _ => return None,
+    };
+
+    Some(escaped.to_string())
+}
+
+fn unicode_escape(c: char) -> String {
+    format!("\\u{{{:x}}}", c as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::{ast::TemplateElement, parser::parse_hurl_file};
+    use pretty_assertions::assert_eq;
+
+    use super::{key_template, text_template};
+
+    fn parsed_header(key: &str, value: &str) -> (String, String) {
+        let encoded = |template: hurl_core::ast::Template| match &template.elements[..] {
+            [TemplateElement::String { encoded, .. }] => encoded.clone(),
+            _ => unreachable!(),
+        };
+        let file = format!(
+            "GET http://example.com\n{}: {}\n",
+            encoded(key_template(key.to_string())),
+            encoded(text_template(value.to_string()))
+        );
+        let header = &parse_hurl_file(&file).unwrap().entries[0].request.headers[0];
+
+        (header.key.to_string(), header.value.to_string())
+    }
+
+    #[test]
+    fn templates_escape_what_hurl_would_not_read_as_text() {
+        assert_eq!(
+            (
+                "[my key]: #1".to_string(),
+                "  #cozy {{not a variable}} {{{ \\o/\t ".to_string()
+            ),
+            parsed_header("[my key]: #1", "  #cozy {{not a variable}} {{{ \\o/\t ")
+        );
+    }
+
+    #[test]
+    fn text_template_keeps_new_uuid_template() {
+        let (_, value) = parsed_header("Id", "{{{newUuid}}");
+        assert_eq!("{{{newUuid}}", value);
+    }
+}
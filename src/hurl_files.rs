@@ -3,19 +3,24 @@ use crate::{
         ErrorHandling::{Log, Terminate},
         ResponseValidationChoice,
     },
-    custom_hurl_ast::{empty_source_info, empty_space, newline},
+    custom_hurl_ast::{empty_source_info, empty_space, key_value, newline},
     errors::OperationError,
-    request_body::request_body::SpecBodySettings,
+    raw_spec::{RawNode, RawSpec},
+    request_body::request_body::{
+        param_value_from_json, SpecBody, SpecBodySettings, SpecBodyValue,
+    },
     response::response_validation::{
-        validate_response_not_error, validation_response_full, HandleUnionsBy,
+        accepted_media_type, documented_status_codes, validate_response_status,
+        validation_response_full, HandleUnionsBy,
     },
-    settings::Settings,
+    sample_values::values::{declared_value, SampleValues, NEW_UUID},
+    settings::{ResponseCodeChoice, Settings},
 };
 use hurl_core::ast::{
     Body, Entry, EntryOption, HurlFile, KeyValue, Method, Request, Section, Template,
     TemplateElement, VariableDefinition, VariableValue, Whitespace,
 };
-use log::{error, trace};
+use log::{error, trace, warn};
 use oas3::{
     spec::{
         FromRef, ObjectOrReference, Operation, Parameter, ParameterIn, PathItem, RefError,
@@ -28,6 +33,16 @@ use crate::request_body;
 
 type OApiPath<'a> = (&'a String, &'a PathItem);
 
+const CONTENT_TYPE_HEADER: &str = "Content-Type";
+const ACCEPT_HEADER: &str = "Accept";
+/// Headers named like idempotency keys need a unique value even without a `uuid` format
+const IDEMPOTENCY: &str = "idempotency";
+const UUID_FORMAT: &str = "uuid";
+/// Path param values that are unlikely to identify an existing resource, to trigger a `404`
+const MISSING_NUMBER_ID: &str = "999999999";
+const MISSING_UUID: &str = "00000000-0000-0000-0000-000000000000";
+const MISSING_STRING_ID: &str = "nonexistent";
+
 pub struct HurlFiles {
     pub hurl_files: Vec<LocalHurlFile>,
     pub errors: Vec<OperationError>,
@@ -40,8 +55,13 @@ pub struct LocalHurlFile {
 }
 
 impl HurlFiles {
-    pub fn from_oai_path(path: OApiPath, spec: &Spec, args: &Settings) -> HurlFiles {
-        HurlFileBuilder::new(&path, spec, args)
+    pub fn from_oai_path(
+        path: OApiPath,
+        spec: &Spec,
+        raw_spec: &RawSpec,
+        args: &Settings,
+    ) -> HurlFiles {
+        HurlFileBuilder::new(&path, spec, raw_spec, args)
             .add_operation(&path.1.get, &HttpMethod::GET)
             .add_operation(&path.1.post, &HttpMethod::POST)
             .add_operation(&path.1.put, &HttpMethod::PUT)
@@ -58,6 +78,7 @@ struct HurlFileBuilder<'a> {
     errors: Vec<OperationError>,
     path: &'a OApiPath<'a>,
     spec: &'a Spec,
+    raw_spec: &'a RawSpec,
     args: &'a Settings,
 }
 
@@ -71,12 +92,18 @@ fn share_element(vec1: &Vec<String>, vec2: &Vec<String>) -> bool {
 }
 
 impl<'a> HurlFileBuilder<'a> {
-    pub fn new(path: &'a OApiPath, spec: &'a Spec, args: &'a Settings) -> HurlFileBuilder<'a> {
+    pub fn new(
+        path: &'a OApiPath,
+        spec: &'a Spec,
+        raw_spec: &'a RawSpec,
+        args: &'a Settings,
+    ) -> HurlFileBuilder<'a> {
         Self {
             hurl_files: vec![],
             errors: vec![],
             path,
             spec,
+            raw_spec,
             args,
         }
     }
@@ -103,7 +130,9 @@ impl<'a> HurlFileBuilder<'a> {
             return self;
         }
 
-        match to_file(*self.path, self.spec, &o, &method, self.args) {
+        let raw_path_item = self.raw_spec.root().child("paths").child(self.path.0);
+
+        match to_file(*self.path, self.spec, o, raw_path_item, method, self.args) {
             Ok(file) => self.hurl_files.push(LocalHurlFile {
                 file,
                 method: method.to_string(),
@@ -130,20 +159,125 @@ fn schema_type_from_schema_type_set(schema_type_set: Option<SchemaTypeSet>) -> S
     }
 }
 
+/// How a request is arranged so the API responds with the status of the validated response.
+#[derive(Clone, Copy, PartialEq)]
+enum StatusTrigger {
+    /// A valid request, for success responses and statuses this tool can't trigger.
+    Valid,
+    /// The path params identify a resource that doesn't exist.
+    MissingResource,
+    /// The JSON request body leaves out its required properties.
+    InvalidBody,
+    /// The `header-vars`, e.g. credentials, are left out.
+    NoCustomHeaders,
+}
+
+impl StatusTrigger {
+    /// `None` for error statuses this tool can't trigger, e.g. `403` or `5XX`, or that the
+    /// request can't be arranged for, e.g. `404` without path params or `401` without any
+    /// `header-vars` to leave out.
+    fn for_response(
+        response_key: Option<&str>,
+        has_path_params: bool,
+        has_custom_headers: bool,
+    ) -> Option<Self> {
+        match response_key {
+            Some("404") | Some("410") if has_path_params => Some(Self::MissingResource),
+            Some("400") | Some("422") => Some(Self::InvalidBody),
+            Some("401") if has_custom_headers => Some(Self::NoCustomHeaders),
+            Some(key) if key.starts_with(['4', '5']) => None,
+            _ => Some(Self::Valid),
+        }
+    }
+}
+
+/// A file with the entries of every response chosen to be validated.
 fn to_file(
     path: OApiPath,
     spec: &Spec,
     operation: &Operation,
+    raw_path_item: RawNode,
     method: &HttpMethod,
     settings: &Settings,
 ) -> Result<HurlFile, Vec<OperationError>> {
+    let response_keys = match (&settings.validate_response, &settings.response_code) {
+        (ResponseValidationChoice::None, _) | (_, ResponseCodeChoice::Success) => vec![None],
+        (_, ResponseCodeChoice::Code(code)) => vec![Some(code.clone())],
+        (_, ResponseCodeChoice::All) => {
+            let codes = documented_status_codes(operation);
+            match codes.is_empty() {
+                true => vec![None],
+                false => codes.into_iter().map(Some).collect(),
+            }
+        }
+    };
+
+    let mut entries = vec![];
+    for response_key in response_keys {
+        entries.extend(operation_entries(
+            path,
+            spec,
+            operation,
+            raw_path_item,
+            method,
+            settings,
+            response_key.as_deref(),
+        )?);
+    }
+
+    // Entries are separated by an empty line
+    for entry in entries.iter_mut().skip(1) {
+        entry.request.line_terminators = vec![newline()];
+    }
+
+    Ok(HurlFile {
+        entries,
+        line_terminators: vec![],
+    })
+}
+
+/// The entries of an operation validating the response documented under the key, or the success
+/// response if there's no key.
+fn operation_entries(
+    path: OApiPath,
+    spec: &Spec,
+    operation: &Operation,
+    raw_path_item: RawNode,
+    method: &HttpMethod,
+    settings: &Settings,
+    response_key: Option<&str>,
+) -> Result<Vec<Entry>, Vec<OperationError>> {
+    let raw_operation = raw_path_item.child(&method.to_string().to_lowercase());
     let mut sections: Vec<Section> = vec![];
     let mut options: Vec<EntryOption> = vec![];
 
-    let param_result_iter = operation.parameters.iter().map(|p| match p {
-        ObjectOrReference::Object(p) => Ok(p.clone()),
-        ObjectOrReference::Ref { ref_path } => Parameter::from_ref(&spec, &ref_path),
+    let param_result_iter = operation_parameters(operation, path.1, spec).into_iter();
+
+    // An undocumented response isn't validated, so the request isn't arranged to trigger it
+    let documented_key = response_key.filter(|key| {
+        operation
+            .responses
+            .as_ref()
+            .is_some_and(|r| r.contains_key(*key))
     });
+    let has_path_params = param_result_iter
+        .clone()
+        .any(|p| p.is_ok_and(|p| p.location == ParameterIn::Path));
+    let trigger = match StatusTrigger::for_response(
+        documented_key,
+        has_path_params,
+        !settings.custom_variables.headers.is_empty(),
+    ) {
+        Some(trigger) => trigger,
+        None => {
+            warn!(
+                "operation {} can't be made to respond with {}, not generating an entry for it",
+                operation.operation_id.as_deref().unwrap_or_default(),
+                response_key.unwrap_or_default()
+            );
+            return Ok(vec![]);
+        }
+    };
 
     let opertation_id = operation.operation_id.clone();
     let mut errors = param_result_iter
@@ -154,19 +288,36 @@ fn to_file(
         })
         .collect::<Vec<OperationError>>();
 
-    let request_body =
-        match parse_request_body(operation, spec, SpecBodySettings::from_settings(settings)) {
-            Ok(r) => r,
-            Err(e) => {
-                errors.push(OperationError::Ref(opertation_id.clone(), e));
-                return Err(errors);
-            }
-        };
+    let body_settings = SpecBodySettings {
+        leave_out_required: trigger == StatusTrigger::InvalidBody,
+        ..SpecBodySettings::from_settings(settings)
+    };
+    let request_bodies = match parse_request_body(
+        operation,
+        spec,
+        raw_operation.child("requestBody"),
+        body_settings,
+    ) {
+        Ok(r) => r,
+        Err(e) => {
+            errors.push(OperationError::Ref(opertation_id.clone(), e));
+            return Err(errors);
+        }
+    };
 
     if errors.len() > 0 {
         return Err(errors);
     }
 
+    if trigger == StatusTrigger::InvalidBody && request_bodies.is_empty() {
+        warn!(
+            "operation {} has no JSON request body with required properties to leave out, not generating an entry for {}",
+            opertation_id.as_deref().unwrap_or_default(),
+            response_key.unwrap_or_default()
+        );
+        return Ok(vec![]);
+    }
+
     let param_iter = param_result_iter.clone().filter_map(|p| match p {
         Ok(p) => Some(p),
         Err(_) => None,
@@ -176,17 +327,34 @@ fn to_file(
         .clone()
         .filter(|p| p.location == ParameterIn::Path);
 
+    let unique_headers: Vec<KeyValue> = param_iter
+        .clone()
+        .filter(|p| p.location == ParameterIn::Header)
+        .filter_map(|p| {
+            let mut schema = p.schema.clone().unwrap_or_default();
+            if schema.format.is_none() && p.name.to_lowercase().contains(IDEMPOTENCY) {
+                schema.format = Some(UUID_FORMAT.to_string());
+            }
+
+            settings
+                .sample_values
+                .dynamic_string(&schema)
+                .map(|value| key_value(p.name.clone(), value))
+        })
+        .collect();
+
     let mut query_params = param_iter.filter(|p| p.location == ParameterIn::Query);
 
     let mut uri = path_params.fold(path.0.clone(), |uri, param| {
         let schema = &param.schema.unwrap_or(Schema::default());
 
-        let default_value = match &schema.example {
-            Some(example) => example.to_string().replace("\"", ""),
-            None => path_param_from_schema_type(schema_type_from_schema_type_set(
-                schema.schema_type.clone(),
-            ))
-            .to_string(),
+        let default_value = match trigger {
+            StatusTrigger::MissingResource => missing_resource_value(schema),
+            _ => param_value_from_schema(
+                schema,
+                raw_param_schema(raw_operation, raw_path_item, &param.name, &param.location),
+                &settings.sample_values,
+            ),
         };
 
         match settings.path_params_choice {
@@ -235,6 +403,52 @@ fn to_file(
         });
     }
 
+    let mut headers: Vec<KeyValue> = settings
+        .custom_variables
+        .headers
+        .iter()
+        .filter(|_| trigger != StatusTrigger::NoCustomHeaders)
+        .map(|kv| KeyValue {
+            key: Template {
+                delimiter: None,
+                elements: vec![TemplateElement::String {
+                    value: "".to_string(),
+                    encoded: kv.0.clone(),
+                }],
+                source_info: empty_source_info(),
+            },
+            value: Template {
+                delimiter: None,
+                elements: vec![TemplateElement::String {
+                    value: "".to_string(),
+                    encoded: format!("{{{{{}}}}}", kv.0.clone()),
+                }],
+                source_info: empty_source_info(),
+            },
+            line_terminators: vec![],
+            space0: empty_space(),
+            space1: empty_space(),
+            space2: single_space(),
+            line_terminator0: newline(),
+        })
+        .collect();
+
+    headers.extend(unique_headers);
+
+    let accept_header = match settings.media_type_headers {
+        true => match accepted_media_type(
+            operation,
+            spec,
+            &settings.content_type,
+            &settings.media_types,
+            response_key,
+        ) {
+            Ok(media_type) => media_type.map(|mt| key_value(ACCEPT_HEADER.to_string(), mt)),
+            Err(e) => return Err(vec![OperationError::Ref(opertation_id, e)]),
+        },
+        false => None,
+    };
+
     match settings.query_params_choice {
         crate::cli::QueryParamChoice::None => (),
         crate::cli::QueryParamChoice::Required => {
@@ -251,13 +465,16 @@ fn to_file(
                         format!(
                             "?{}={}",
                             param.name,
-                            match schema.example {
-                                Some(e) => e.to_string().replace("\"", ""),
-                                None => path_param_from_schema_type(
-                                    schema_type_from_schema_type_set(schema.schema_type)
-                                )
-                                .to_string(),
-                            }
+                            param_value_from_schema(
+                                &schema,
+                                raw_param_schema(
+                                    raw_operation,
+                                    raw_path_item,
+                                    &param.name,
+                                    &param.location
+                                ),
+                                &settings.sample_values,
+                            )
                         )
                     }
                     None => "".to_string(),
@@ -272,15 +489,16 @@ fn to_file(
                 format!(
                     "{uri}&{}={}",
                     param.name,
-                    match schema.example {
-                        Some(e) => {
-                            e.to_string().replace("\"", "")
-                        }
-                        None => path_param_from_schema_type(schema_type_from_schema_type_set(
-                            schema.schema_type
-                        ))
-                        .to_string(),
-                    }
+                    param_value_from_schema(
+                        &schema,
+                        raw_param_schema(
+                            raw_operation,
+                            raw_path_item,
+                            &param.name,
+                            &param.location
+                        ),
+                        &settings.sample_values,
+                    )
                 )
             });
         }
@@ -296,13 +514,16 @@ fn to_file(
                         format!(
                             "?{}={}",
                             param.name,
-                            match schema.example {
-                                Some(e) => e.to_string().replace("\"", ""),
-                                None => path_param_from_schema_type(
-                                    schema_type_from_schema_type_set(schema.schema_type)
-                                )
-                                .to_string(),
-                            }
+                            param_value_from_schema(
+                                &schema,
+                                raw_param_schema(
+                                    raw_operation,
+                                    raw_path_item,
+                                    &param.name,
+                                    &param.location
+                                ),
+                                &settings.sample_values,
+                            )
                         )
                     }
                     None => "".to_string(),
@@ -317,124 +538,166 @@ fn to_file(
                 format!(
                     "{uri}&{}={}",
                     param.name,
-                    match schema.example {
-                        Some(e) => {
-                            e.to_string().replace("\"", "")
-                        }
-                        None => path_param_from_schema_type(schema_type_from_schema_type_set(
-                            schema.schema_type
-                        ))
-                        .to_string(),
-                    }
+                    param_value_from_schema(
+                        &schema,
+                        raw_param_schema(
+                            raw_operation,
+                            raw_path_item,
+                            &param.name,
+                            &param.location
+                        ),
+                        &settings.sample_values,
+                    )
                 )
             });
         }
     };
 
-    let entry = Entry {
-        request: Request {
-            line_terminators: vec![],
-            space0: Whitespace {
-                value: "".to_string(),
-                source_info: empty_source_info(),
-            },
-            method: Method(method.to_string()),
-            space1: Whitespace {
-                value: " ".to_string(),
-                source_info: empty_source_info(),
-            },
-            url: Template {
-                delimiter: None,
-                elements: vec![TemplateElement::String {
-                    value: "".to_string(),
-                    encoded: format!("{{{{host}}}}{uri}"),
-                }],
-                source_info: empty_source_info(),
-            },
-            line_terminator0: newline(),
-            headers: settings
-                .custom_variables
-                .headers
-                .iter()
-                .map(|kv| KeyValue {
-                    key: Template {
-                        delimiter: None,
-                        elements: vec![TemplateElement::String {
-                            value: "".to_string(),
-                            encoded: kv.0.clone(),
-                        }],
+    let response = match settings.validate_response {
+        ResponseValidationChoice::None => None,
+        ResponseValidationChoice::NonErrorCode => {
+            match validate_response_status(operation, spec, response_key) {
+                Ok(response) => response,
+                Err(e) => {
+                    match settings.error_handling {
+                        Log => error!("{}", OperationError::Ref(opertation_id, e)),
+                        Terminate => return Err(vec![OperationError::Ref(opertation_id, e)]),
+                    };
+                    None
+                }
+            }
+        }
+        ResponseValidationChoice::Body => {
+            match validation_response_full(
+                operation,
+                raw_operation,
+                spec,
+                settings,
+                HandleUnionsBy::IgnoringThem,
+                response_key,
+            ) {
+                Ok(response) => response,
+                Err(e) => {
+                    match settings.error_handling {
+                        Log => error!("{}", OperationError::Ref(opertation_id, e)),
+                        Terminate => return Err(vec![OperationError::Ref(opertation_id, e)]),
+                    };
+                    None
+                }
+            }
+        }
+        ResponseValidationChoice::BodyWithOptionals => {
+            match validation_response_full(
+                operation,
+                raw_operation,
+                spec,
+                settings,
+                HandleUnionsBy::TreatingOptionalsAsRequired,
+                response_key,
+            ) {
+                Ok(response) => response,
+                Err(e) => {
+                    match settings.error_handling {
+                        Log => error!("{}", OperationError::Ref(opertation_id, e)),
+                        Terminate => return Err(vec![OperationError::Ref(opertation_id, e)]),
+                    };
+                    None
+                }
+            }
+        }
+    };
+
+    // An operation without a request body still gets an entry
+    let request_bodies = match request_bodies.is_empty() {
+        true => vec![None],
+        false => request_bodies.into_iter().map(Some).collect(),
+    };
+
+    let entries = request_bodies
+        .into_iter()
+        .map(|request_body| {
+            let mut headers = headers.clone();
+            let mut sections = sections.clone();
+            let body = add_request_body(request_body, &mut headers, &mut sections, settings);
+            headers.extend(accept_header.clone());
+
+            Entry {
+                request: Request {
+                    line_terminators: vec![],
+                    space0: Whitespace {
+                        value: "".to_string(),
+                        source_info: empty_source_info(),
+                    },
+                    method: Method(method.to_string()),
+                    space1: Whitespace {
+                        value: " ".to_string(),
                         source_info: empty_source_info(),
                     },
-                    value: Template {
+                    url: Template {
                         delimiter: None,
                         elements: vec![TemplateElement::String {
                             value: "".to_string(),
-                            encoded: format!("{{{{{}}}}}", kv.0.clone()),
+                            encoded: format!("{{{{host}}}}{uri}"),
                         }],
                         source_info: empty_source_info(),
                     },
-                    line_terminators: vec![],
-                    space0: empty_space(),
-                    space1: empty_space(),
-                    space2: single_space(),
                     line_terminator0: newline(),
-                })
-                .collect(),
-            sections,
-            body: request_body,
-            source_info: empty_source_info(),
-        },
-        response: match settings.validate_response {
-            ResponseValidationChoice::None => None,
-            ResponseValidationChoice::NonErrorCode => Some(validate_response_not_error()),
-            ResponseValidationChoice::Body => {
-                match validation_response_full(
-                    operation,
-                    spec,
-                    &settings.content_type,
-                    HandleUnionsBy::IgnoringThem,
-                ) {
-                    Ok(response) => response,
-                    Err(e) => {
-                        match settings.error_handling {
-                            Log => error!("{}", OperationError::Ref(opertation_id, e)),
-                            Terminate => return Err(vec![OperationError::Ref(opertation_id, e)]),
-                        };
-                        None
-                    }
-                }
-            }
-            ResponseValidationChoice::BodyWithOptionals => {
-                match validation_response_full(
-                    operation,
-                    spec,
-                    &settings.content_type,
-                    HandleUnionsBy::TreatingOptionalsAsRequired,
-                ) {
-                    Ok(response) => response,
-                    Err(e) => {
-                        match settings.error_handling {
-                            Log => error!("{}", OperationError::Ref(opertation_id, e)),
-                            Terminate => return Err(vec![OperationError::Ref(opertation_id, e)]),
-                        };
-                        None
-                    }
-                }
+                    headers,
+                    sections,
+                    body,
+                    source_info: empty_source_info(),
+                },
+                response: response.clone(),
             }
-        },
-    };
+        })
+        .collect();
 
-    Ok(HurlFile {
-        entries: vec![entry],
-        line_terminators: vec![],
-    })
+    Ok(entries)
+}
+
+/// Adds the request body to the request, forms are sections of the request and files need their
+/// content type set.
+fn add_request_body(
+    request_body: Option<SpecBody>,
+    headers: &mut Vec<KeyValue>,
+    sections: &mut Vec<Section>,
+    settings: &Settings,
+) -> Option<Body> {
+    match request_body {
+        Some(SpecBody {
+            media_type,
+            value: SpecBodyValue::Body(body),
+        }) => {
+            if settings.media_type_headers {
+                headers.push(key_value(CONTENT_TYPE_HEADER.to_string(), media_type));
+            }
+            Some(body)
+        }
+        // Hurl sets the content type of forms
+        Some(SpecBody {
+            value: SpecBodyValue::Section(section),
+            ..
+        }) => {
+            sections.push(section);
+            None
+        }
+        Some(SpecBody {
+            media_type,
+            value: SpecBodyValue::File(body),
+        }) => {
+            headers.push(key_value(CONTENT_TYPE_HEADER.to_string(), media_type));
+            Some(body)
+        }
+        None => None,
+    }
 }
 
 fn parse_request_body(
     operation: &Operation,
     spec: &Spec,
+    raw_body: RawNode,
     settings: SpecBodySettings,
-) -> Result<Option<Body>, RefError> {
+) -> Result<Vec<SpecBody>, RefError> {
     let operation_id = operation
         .operation_id
         .clone()
@@ -444,7 +707,7 @@ fn parse_request_body(
         Some(b) => b,
         None => {
             trace!("No request body found for {}", operation_id);
-            return Ok(None);
+            return Ok(vec![]);
         }
     };
 
@@ -464,11 +727,116 @@ fn parse_request_body(
     Ok(request_body::request_body::from_spec_body(
         body,
         spec,
+        raw_body,
         operation_id,
         settings,
     )?)
 }
 
+/// The value of a path or query parameter, percent-encoded so it can be put into the URI.
+fn param_value_from_schema(schema: &Schema, raw: RawNode, sample_values: &SampleValues) -> String {
+    let value = match declared_value(schema, raw) {
+        Some(value) => param_value_from_json(&value),
+        None => match schema_type_from_schema_type_set(schema.schema_type.clone()) {
+            SchemaType::String => sample_values.string_from_schema(schema, "string_value"),
+            SchemaType::Integer => sample_values.integer_from_schema(schema, 3).to_string(),
+            SchemaType::Number => sample_values.number_from_schema(schema, 5.5).to_string(),
+            SchemaType::Boolean => sample_values.boolean(true).to_string(),
+            t => return path_param_from_schema_type(t).to_string(),
+        },
+    };
+
+    percent_encode(&value)
+}
+
+/// Encodes every character that isn't unreserved in URIs, e.g. `12:00` becomes `12%3A00`. The
+/// `{{newUuid}}` template of dynamic values is kept, hurl generates a UUID in its place.
+fn percent_encode(value: &str) -> String {
+    value
+        .split(NEW_UUID)
+        .map(|part| {
+            part.bytes()
+                .map(|b| match b {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                        (b as char).to_string()
+                    }
+                    b => format!("%{b:02X}"),
+                })
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(NEW_UUID)
+}
+
+/// A path param value that's unlikely to identify an existing resource.
+fn missing_resource_value(schema: &Schema) -> String {
+    let schema_type = schema_type_from_schema_type_set(schema.schema_type.clone());
+    match (schema_type, schema.format.as_deref()) {
+        (SchemaType::Integer | SchemaType::Number, _) => MISSING_NUMBER_ID.to_string(),
+        (_, Some(UUID_FORMAT)) => MISSING_UUID.to_string(),
+        _ => MISSING_STRING_ID.to_string(),
+    }
+}
+
+/// The operation's parameters followed by the parameters of its path item, unless the operation
+/// overrides them with a parameter of the same name and location.
+/// See https://spec.openapis.org/oas/v3.1.0#path-item-object
+fn operation_parameters(
+    operation: &Operation,
+    path_item: &PathItem,
+    spec: &Spec,
+) -> Vec<Result<Parameter, RefError>> {
+    let resolve = |p: &ObjectOrReference<Parameter>| match p {
+        ObjectOrReference::Object(p) => Ok(p.clone()),
+        ObjectOrReference::Ref { ref_path } => Parameter::from_ref(spec, ref_path),
+    };
+
+    let mut parameters: Vec<_> = operation.parameters.iter().map(resolve).collect();
+    for parameter in path_item.parameters.iter().map(resolve) {
+        let is_overridden = parameter.as_ref().is_ok_and(|p| {
+            parameters
+                .iter()
+                .flatten()
+                .any(|o| o.name == p.name && o.location == p.location)
+        });
+        if !is_overridden {
+            parameters.push(parameter);
+        }
+    }
+
+    parameters
+}
+
+/// The raw schema of the operation's parameter, or of the path item's parameter if the operation
+/// doesn't declare it.
+fn raw_param_schema<'a>(
+    raw_operation: RawNode<'a>,
+    raw_path_item: RawNode<'a>,
+    name: &str,
+    location: &ParameterIn,
+) -> RawNode<'a> {
+    let location = match location {
+        ParameterIn::Path => "path",
+        ParameterIn::Query => "query",
+        ParameterIn::Header => "header",
+        ParameterIn::Cookie => "cookie",
+    };
+
+    let is_param = |p: &RawNode| {
+        p.get("name").and_then(|n| n.as_str()) == Some(name)
+            && p.get("in").and_then(|l| l.as_str()) == Some(location)
+    };
+
+    let param = raw_operation.child("parameters").find(is_param);
+    match param.get("name") {
+        Some(_) => param.child("schema"),
+        None => raw_path_item
+            .child("parameters")
+            .find(is_param)
+            .child("schema"),
+    }
+}
+
 fn path_param_from_schema_type(schema_type: SchemaType) -> &'static str {
     match schema_type {
         SchemaType::Boolean => "true",
@@ -5,6 +5,7 @@ use crate::{
 use std::{
     fs::{self, File},
     io::{self, IsTerminal, Write},
+    path::Path,
 };
 
 use crate::cli::Cli;
@@ -14,19 +15,27 @@ use cli::{ErrorHandling, Grouping};
 use errors::OperationError;
 use hurl_files::HurlFiles;
 use log::{error, info, trace};
-use oas3::Spec;
+use regex::Regex;
+use spec_reader::SpecDocument;
 
 mod cli;
 mod content_type;
 mod custom_hurl_ast;
 mod errors;
 mod hurl_files;
+mod raw_spec;
 mod request_body;
 mod response;
+mod sample_values;
+mod schema_ancestors;
+mod schema_composition;
 mod settings;
 mod spec_reader;
 mod variable_files;
 
+const UPLOAD_FILE_EXTENSION: &str = "bin";
+const UPLOAD_FILE_PLACEHOLDER: &str = "Replace this file with the content to upload\n";
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
     let grouping = cli.grouping.clone();
@@ -45,9 +54,13 @@ fn main() -> Result<()> {
         .init()
         .unwrap();
 
+    if let Some(seed) = args.sample_values.seed() {
+        info!("Generating random data with seed {seed}");
+    }
+
     trace!("parsing oas3 from path");
 
-    let spec = match &args.input {
+    let document = match &args.input {
         Some(p) => spec_reader::from_path(p.to_path_buf())?,
         None => {
             let stdin = io::stdin().lock();
@@ -61,9 +74,9 @@ fn main() -> Result<()> {
     };
 
     trace!("transforming oas3 to hurl files");
-    let hurl_files = hurl_files_from_spec_path(&args, &spec)?;
+    let hurl_files = hurl_files_from_spec_path(&args, &document)?;
     trace!("transforming oas3 to hurl variables file");
-    let variable_files = VariableFiles::from_spec(&spec, args.custom_variables);
+    let variable_files = VariableFiles::from_spec(&document.spec, args.custom_variables);
 
     trace!("returning values out");
     match args.out_dir {
@@ -90,6 +103,7 @@ fn out_to_files(
     grouping: Grouping,
 ) -> Result<()> {
     let mut files_created_count = 0;
+    let mut upload_variables = vec![];
     match grouping {
         Grouping::Flat => {
             for file_contents in hurl_files {
@@ -100,7 +114,12 @@ fn out_to_files(
 
                     file.write_all(file_string.file.as_bytes())
                         .with_context(|| format!("Could not write to file {file_path}"))?;
-                    files_created_count += 1
+                    files_created_count += 1;
+
+                    upload_variables.extend(create_upload_files(
+                        &out_path.display().to_string(),
+                        &file_string.file,
+                    )?);
                 }
             }
         }
@@ -123,19 +142,30 @@ fn out_to_files(
 
                     file.write_all(file_string.file.as_bytes())
                         .with_context(|| format!("Could not write to file {file_path}"))?;
-                    files_created_count += 1
+                    files_created_count += 1;
+
+                    upload_variables.extend(create_upload_files(&dir_path, &file_string.file)?);
                 }
             }
         }
     }
 
-    for v_file in variable_files.files {
+    for mut v_file in variable_files.files {
         let file_path = format!("{}/{}", out_path.display(), v_file.name);
         let existing_variable_file = match fs::read_to_string(&file_path) {
             Ok(f) => VariableFile::from_string(v_file.name.clone(), f),
             Err(_) => VariableFile::empty(v_file.name.clone()),
         };
 
+        for variable in &upload_variables {
+            if !v_file.key_vals.iter().any(|kv| &kv.0 == variable) {
+                v_file.key_vals.push((
+                    variable.clone(),
+                    format!("{variable}.{UPLOAD_FILE_EXTENSION}"),
+                ));
+            }
+        }
+
         let mut file = File::create(&file_path)
             .with_context(|| format!("Could not open file at {file_path}. Most likely because the directory `{}` does not exist", out_path.display()))?;
 
@@ -156,6 +186,30 @@ fn out_to_files(
     Ok(())
 }
 
+/// Creates a placeholder for every file a hurl file uploads, so the generated requests can run
+/// before the real files are added. Existing files are left alone. Returns the variables used for
+/// the file names.
+fn create_upload_files(dir_path: &str, hurl_file: &str) -> Result<Vec<String>> {
+    let file_variable = Regex::new(r"file,\s*\{\{([\w-]+)\}\};").unwrap();
+    let mut variables = vec![];
+
+    for captures in file_variable.captures_iter(hurl_file) {
+        let variable = captures[1].to_string();
+        let file_path = format!("{dir_path}/{variable}.{UPLOAD_FILE_EXTENSION}");
+
+        if !Path::new(&file_path).exists() {
+            fs::write(&file_path, UPLOAD_FILE_PLACEHOLDER)
+                .with_context(|| format!("Could not write to file {file_path}"))?;
+        }
+
+        if !variables.contains(&variable) {
+            variables.push(variable);
+        }
+    }
+
+    Ok(variables)
+}
+
 #[derive(Debug, PartialEq)]
 pub struct HurlFileString {
     pub filename: String,
@@ -188,12 +242,12 @@ fn handle_errors(
 
 fn hurl_files_from_spec_path(
     args: &Settings,
-    spec: &Spec,
+    document: &SpecDocument,
 ) -> Result<Vec<(String, Vec<HurlFileString>)>, anyhow::Error> {
     let mut files = vec![];
-    for path in spec.paths.iter() {
+    for path in document.spec.paths.iter() {
         for p in path {
-            let hurl_files = HurlFiles::from_oai_path(p, &spec, &args);
+            let hurl_files = HurlFiles::from_oai_path(p, &document.spec, &document.raw, args);
 
             if hurl_files.errors.len() > 0 {
                 handle_errors(hurl_files.errors, &args.error_handling)?
@@ -220,15 +274,20 @@ fn hurl_files_from_spec_path(
 
 #[cfg(test)]
 mod tests {
-    use crate::Settings;
+    use crate::{settings::ResponseCodeChoice, Settings};
     use pretty_assertions::assert_eq;
     use serde_json::json;
     use std::{path::PathBuf, str::FromStr};
 
     use crate::{
-        cli::{Formatting, QueryParamChoice, ResponseValidationChoice},
+        cli::{
+            BodyFieldChoice, Formatting, NullValueChoice, QueryParamChoice, RequestMediaTypeChoice,
+            ResponseValidationChoice, UnionVariantChoice,
+        },
         content_type::ContentType,
         hurl_files_from_spec_path,
+        sample_values::values::SampleValues,
+        spec_reader,
         variable_files::CustomVariables,
         HurlFileString,
     };
@@ -238,6 +297,7 @@ mod tests {
         json!({
         "id": 3,
         "name": "string",
+        "tag": "string",
         "some_arr_with_no_type": [
             "string"
         ],
@@ -245,7 +305,6 @@ mod tests {
           "https://example.com/img.png",
           "https://example.com/img2.png"
         ],
-        "tag": "string",
         "inner": {
             "test": "string"
         }
@@ -255,11 +314,12 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_pet_store_spec() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -309,12 +369,13 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_pet_store_spec_and_operation_id_selected() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
                 operation_id_selection: Some(vec!["listPets".to_string()]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -333,13 +394,14 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_nonerror_validation_selected() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
                 operation_id_selection: Some(vec!["listPets".to_string()]),
                 validate_response: ResponseValidationChoice::NonErrorCode,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -348,23 +410,50 @@ mod tests {
         let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
             "_pets".to_string(),
             vec![HurlFileString {
-                file: "GET {{host}}/pets?limit=3\n\nHTTP *\n[Asserts]\nstatus < 400".to_string(),
+                file: "GET {{host}}/pets?limit=3\n\nHTTP 200\n".to_string(),
                 filename: "listPets".to_string(),
             }],
         )];
         assert_eq!(expected, result.unwrap());
     }
 
+    #[test]
+    fn hurl_files_from_spec_path_with_nonerror_validation_without_success_response() {
+        let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["createPetById".to_string()]),
+                validate_response: ResponseValidationChoice::NonErrorCode,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets/id_11\n\nHTTP *\n[Asserts]\n\nstatus < 400".to_string(),
+                filename: "createPetById".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
     #[test]
     fn hurl_files_from_spec_path_with_no_formatting() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
                 operation_id_selection: Some(vec!["addPet".to_string()]),
                 formatting: Formatting::NoFormatting,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -387,7 +476,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_pet_store_spec_no_query_params() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -397,6 +486,7 @@ mod tests {
                     "listPets".to_string(),
                     "showPetById".to_string(),
                 ]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -424,7 +514,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_plain_text() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -432,6 +522,7 @@ mod tests {
                 query_params_choice: crate::cli::QueryParamChoice::None,
                 operation_id_selection: Some(vec!["addPet".to_string()]),
                 content_type: ContentType::Text,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -450,7 +541,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_plain_text_and_full_validation() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -459,6 +550,33 @@ mod tests {
                 operation_id_selection: Some(vec!["addPet".to_string()]),
                 content_type: ContentType::Text,
                 validate_response: ResponseValidationChoice::Body,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets\n```\n10,\\\"doggie\\\"\n```\n\nHTTP 201\n[Asserts]\n\nheader \"Content-Type\" startsWith \"text/plain\"\nbody isString\nbody matches /^\\d+,\\d+$/\nbody matches /^.{4}/ #assert min length\nbody matches /^.{0,100}$/ #assert max length".to_string(),
+                filename: "addPet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_path_with_required_body_fields() {
+        let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["addPet".to_string()]),
+                body_fields: BodyFieldChoice::Required,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -467,7 +585,20 @@ mod tests {
         let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
             "_pets".to_string(),
             vec![HurlFileString {
-                file: "POST {{host}}/pets\n```\n10,\\\"doggie\\\"\n```\n\nHTTP *\n[Asserts]\n\nstatus < 400\nbody isString\nbody matches /^\\d+,\\d+$/\nbody matches /^.{4}/ #assert min length\nbody matches /^.{0,100}$/ #assert max length".to_string(),
+                file: "POST {{host}}/pets\n```json\n".to_string()
+                    + &serde_json::to_string_pretty(&json!({
+                        "id": 3,
+                        "name": "string",
+                        "photo_urls": [
+                            "https://example.com/img.png",
+                            "https://example.com/img2.png"
+                        ],
+                        "inner": {
+                            "test": "string"
+                        }
+                    }))
+                    .unwrap()
+                    + "\n```\n",
                 filename: "addPet".to_string(),
             }],
         )];
@@ -477,7 +608,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_json_and_full_validation() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -486,6 +617,7 @@ mod tests {
                 operation_id_selection: Some(vec!["addPet".to_string()]),
                 content_type: ContentType::Json,
                 validate_response: ResponseValidationChoice::Body,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -496,13 +628,13 @@ mod tests {
             vec![HurlFileString {
                 file: "POST {{host}}/pets\n```json\n".to_string()
                     + &serde_json::to_string_pretty(&get_add_pet_request_body()).unwrap()
-                    + "\n```\n\nHTTP *"
-                    + "\n[Asserts]"
-                    + "\n\nstatus < 400"
+                    + "\n```\n\nHTTP 201"
+                    + "\n[Asserts]\n"
+                    + "\nheader \"Content-Type\" startsWith \"application/json\""
                     + "\njsonpath \"$\" isCollection"
                     + "\njsonpath \"$.id\" isInteger"
-                    + "\njsonpath \"$.inner\" isCollection\njsonpath \"$.inner.test\" isString"
-                    + "\njsonpath \"$.name\" isString\njsonpath \"$.photo_urls\" isCollection",
+                    + "\njsonpath \"$.name\" isString\njsonpath \"$.photo_urls\" isCollection"
+                    + "\njsonpath \"$.inner\" isCollection\njsonpath \"$.inner.test\" isString",
                 filename: "addPet".to_string(),
             }],
         )];
@@ -512,7 +644,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_path_with_json_and_full_with_optional_validation() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -521,6 +653,7 @@ mod tests {
                 operation_id_selection: Some(vec!["addPet".to_string()]),
                 content_type: ContentType::Json,
                 validate_response: ResponseValidationChoice::BodyWithOptionals,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -531,15 +664,15 @@ mod tests {
             vec![HurlFileString {
                 file: "POST {{host}}/pets\n```json\n".to_string()
                     + &serde_json::to_string_pretty(&get_add_pet_request_body()).unwrap()
-                    + "\n```\n\nHTTP *"
-                    + "\n[Asserts]"
-                    + "\n\nstatus < 400"
+                    + "\n```\n\nHTTP 201"
+                    + "\n[Asserts]\n"
+                    + "\nheader \"Content-Type\" startsWith \"application/json\""
                     + "\njsonpath \"$\" isCollection"
                     + "\njsonpath \"$.id\" isInteger"
-                    + "\njsonpath \"$.inner\" isCollection\njsonpath \"$.inner.test\" isString"
-                    + "\njsonpath \"$.name\" isString\njsonpath \"$.photo_urls\" isCollection"
+                    + "\njsonpath \"$.name\" isString\njsonpath \"$.tag\" isString"
                     + "\njsonpath \"$.some_arr_with_no_type\" isCollection"
-                    + "\njsonpath \"$.tag\" isString",
+                    + "\njsonpath \"$.photo_urls\" isCollection"
+                    + "\njsonpath \"$.inner\" isCollection\njsonpath \"$.inner.test\" isString",
                 filename: "addPet".to_string(),
             }],
         )];
@@ -550,7 +683,7 @@ mod tests {
     fn hurl_files_from_spec_path_with_plain_text_option_but_no_plain_text_in_schema_selects_first_valid(
     ) {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -559,6 +692,7 @@ mod tests {
                 operation_id_selection: Some(vec!["updatePet".to_string()]),
                 content_type: ContentType::Text,
                 formatting: Formatting::NoFormatting,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -581,7 +715,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_with_no_response_validation_with_all_query_params() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
@@ -591,6 +725,7 @@ mod tests {
                     "listPets".to_string(),
                     "showPetById".to_string(),
                 ]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -618,7 +753,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_with_header_variables() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
@@ -634,6 +769,7 @@ mod tests {
                     "listPets".to_string(),
                     "showPetById".to_string(),
                 ]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -663,13 +799,14 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_using_tag_filter_returns_only_those_in_tag() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
                 input: Some(spec_path),
                 query_params_choice: crate::cli::QueryParamChoice::None,
                 tags: Some(vec!["petsRead".to_string()]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -697,7 +834,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_with_no_expected_status_code() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -705,6 +842,7 @@ mod tests {
                 query_params_choice: crate::cli::QueryParamChoice::None,
                 operation_id_selection: Some(vec!["createPetById".to_string()]),
                 validate_response: ResponseValidationChoice::Body,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -713,7 +851,7 @@ mod tests {
         let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
             "_pets_{petId}".to_string(),
             vec![HurlFileString {
-                file: "POST {{host}}/pets/id_11\n\nHTTP *\n[Asserts]\n\njsonpath \"$\" isCollection\njsonpath \"$.code\" isInteger\njsonpath \"$.message\" isString".to_string(),
+                file: "POST {{host}}/pets/id_11\n\nHTTP *\n[Asserts]\n\nheader \"Content-Type\" startsWith \"application/json\"\njsonpath \"$\" isCollection\njsonpath \"$.code\" isInteger\njsonpath \"$.message\" isString".to_string(),
                 filename: "createPetById".to_string(),
             }],
         )];
@@ -723,7 +861,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_with_no_expected_status_code_plain_text() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -732,6 +870,7 @@ mod tests {
                 operation_id_selection: Some(vec!["createPetById".to_string()]),
                 validate_response: ResponseValidationChoice::Body,
                 content_type: ContentType::Text,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -740,7 +879,7 @@ mod tests {
         let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
             "_pets_{petId}".to_string(),
             vec![HurlFileString {
-                file: "POST {{host}}/pets/id_11\n\nHTTP *\n[Asserts]\n\nbody isString".to_string(),
+                file: "POST {{host}}/pets/id_11\n\nHTTP *\n[Asserts]\n\nheader \"Content-Type\" startsWith \"text/plain\"\nbody isString".to_string(),
                 filename: "createPetById".to_string(),
             }],
         )];
@@ -750,7 +889,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_using_tag_and_oid_filter_returns_only_those_in_tag_and_operation_id() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -758,6 +897,7 @@ mod tests {
                 query_params_choice: crate::cli::QueryParamChoice::None,
                 operation_id_selection: Some(vec!["showPetById".to_string()]),
                 tags: Some(vec!["petsRead".to_string()]),
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -776,7 +916,7 @@ mod tests {
     #[test]
     fn hurl_files_from_spec_with_path_param_variables_option() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
 
         let result = hurl_files_from_spec_path(
             &Settings {
@@ -787,6 +927,7 @@ mod tests {
                     "createPetById".to_string(),
                 ]),
                 path_params_choice: crate::cli::PathParamChoice::Variables,
+                media_type_headers: false,
                 ..Settings::default()
             },
             &spec,
@@ -810,4 +951,903 @@ mod tests {
 
         assert_eq!(expected, result.unwrap());
     }
+
+    #[test]
+    fn hurl_files_from_spec_with_multipart_form_data() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["uploadPetPhoto".to_string()]),
+                content_type: ContentType::MultipartFormData,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}_photos".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets/22/photos\n[MultipartFormData]\n".to_string()
+                    + "caption: Asleep on the couch\n"
+                    + "hashtags: \\#cozy \\u{7b}{not a variable}} \\\\o/\\u{20}\n"
+                    + "metadata: {\"width\":3}\n"
+                    + "photo: file,{{photo_file}}; image/png\n"
+                    + "thumbnails: file,{{thumbnails_file}};\n",
+                filename: "uploadPetPhoto".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_form_url_encoded() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["requestToken".to_string()]),
+                content_type: ContentType::FormUrlEncoded,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_oauth_token".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/oauth/token\n[FormParams]\n".to_string()
+                    + "grant_type: client_credentials\n"
+                    + "scope: pets:read,pets:write\n"
+                    + "audience: pets\n"
+                    + "audience: stores\n"
+                    + "filter[owner]: me\n"
+                    + "filter[tag]: \\u{7b}{good}} \\#1\n",
+                filename: "requestToken".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_xml() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["addPet".to_string()]),
+                content_type: ContentType::Xml,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets\n```xml\n".to_string()
+                    + "<ps:pet xmlns:ps=\"https://example.com/schema\" id=\"10\">\n"
+                    + "  <name>doggie &amp; co</name>\n"
+                    + "  <photos>\n"
+                    + "    <photo>a.png</photo>\n"
+                    + "    <photo>b.png</photo>\n"
+                    + "  </photos>\n"
+                    + "  <tag>\n"
+                    + "    <name>good</name>\n"
+                    + "  </tag>\n"
+                    + "</ps:pet>\n```\n",
+                filename: "addPet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_binary_body() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["replacePetPhoto".to_string()]),
+                content_type: ContentType::Binary,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}_photo".to_string(),
+            vec![HurlFileString {
+                file: "PUT {{host}}/pets/string_value/photo\n".to_string()
+                    + "Content-Type: image/png\n"
+                    + "file,{{upload_file}};\n",
+                filename: "replacePetPhoto".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_media_type_preferences() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["updatePet".to_string()]),
+                validate_response: ResponseValidationChoice::Body,
+                media_types: vec![
+                    "application/vnd.*".to_string(),
+                    "application/*+json".to_string(),
+                ],
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}".to_string(),
+            vec![HurlFileString {
+                file: "PATCH {{host}}/pets/22\n```json\n{\"tag\":\"good\"}\n```\n\n".to_string()
+                    + "HTTP 200\n[Asserts]\n\nheader \"Content-Type\" startsWith \"application/vnd.api+json\"\n"
+                    + "jsonpath \"$\" isCollection\n"
+                    + "jsonpath \"$.data\" isCollection",
+                filename: "updatePet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_media_type_headers() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec![
+                    "updatePet".to_string(),
+                    "uploadPetPhoto".to_string(),
+                ]),
+                media_types: vec!["application/merge-patch+json".to_string()],
+                formatting: Formatting::NoFormatting,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![
+            (
+                "_pets_{petId}".to_string(),
+                vec![HurlFileString {
+                    file: "PATCH {{host}}/pets/22\n".to_string()
+                        + "Content-Type: application/merge-patch+json\n"
+                        + "Accept: application/json\n"
+                        + "```json\n{\"tag\":\"good\"}\n```\n",
+                    filename: "updatePet".to_string(),
+                }],
+            ),
+            (
+                "_pets_{petId}_photos".to_string(),
+                vec![HurlFileString {
+                    file: "POST {{host}}/pets/22/photos\n[MultipartFormData]\n".to_string()
+                        + "caption: Asleep on the couch\n"
+                        + "hashtags: \\#cozy \\u{7b}{not a variable}} \\\\o/\\u{20}\n"
+                        + "metadata: {\"width\":3}\n"
+                        + "photo: file,{{photo_file}}; image/png\n"
+                        + "thumbnails: file,{{thumbnails_file}};\n",
+                    filename: "uploadPetPhoto".to_string(),
+                }],
+            ),
+        ];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_entry_per_request_media_type() {
+        let spec_path = PathBuf::from_str("test_files/pet_store_content_types.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["updatePet".to_string()]),
+                request_media_types: RequestMediaTypeChoice::All,
+                media_type_headers: true,
+                formatting: Formatting::NoFormatting,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}".to_string(),
+            vec![HurlFileString {
+                file: "PATCH {{host}}/pets/22\n".to_string()
+                    + "Content-Type: application/json\n"
+                    + "Accept: application/json\n"
+                    + "```json\n{\"name\":\"doggie\"}\n```\n"
+                    + "\n"
+                    + "PATCH {{host}}/pets/22\n"
+                    + "Content-Type: application/merge-patch+json\n"
+                    + "Accept: application/json\n"
+                    + "```json\n{\"tag\":\"good\"}\n```\n",
+                filename: "updatePet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_formatted_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["bookVisit".to_string()]),
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_owners_{ownerId}_visits".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/owners/3fa85f64-5717-4562-b3fc-2c963f66afa6/visits?since=2024-01-01T12%3A00%3A00Z\n```json\n".to_string()
+                    + &serde_json::to_string_pretty(&json!({
+                        "id": "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                        "date": "2024-01-01",
+                        "email": "user@example.com",
+                        "website": "https://example.com",
+                        "clinicIp": "192.0.2.1",
+                        "clinicIpv6": "2001:db8::1",
+                        "clinicHost": "example.com",
+                        "notes": "c3RyaW5n",
+                        "petIds": [
+                            "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+                            "d6f596f9-b4a2-4ee2-a500-ed0eb61978cc"
+                        ]
+                    }))
+                    .unwrap()
+                    + "\n```\n",
+                filename: "bookVisit".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_constrained_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["registerLitter".to_string()]),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_litters".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/litters?limit=12\n```json\n".to_string()
+                    + "{\"size\":5,\"weight\":5.25,\"code\":\"stringst\",\"names\":[\"stri\",\"vadk\"],"
+                    + "\"temperaments\":[\"calm\",\"playful\"],"
+                    + "\"registration\":\"LIT-0000\",\"breed\":\"mixed\",\"colour\":\"brown\"}"
+                    + "\n```\n",
+                filename: "registerLitter".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_declared_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["searchLitters".to_string()]),
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_litters".to_string(),
+            vec![HurlFileString {
+                file:
+                    "GET {{host}}/litters?breed=mixed&sort=size&page=1&q=say%20%22hi%22%20%5Co%2F\n"
+                        .to_string(),
+                filename: "searchLitters".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_map_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["setPricing".to_string()]),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pricing".to_string(),
+            vec![HurlFileString {
+                file: "PUT {{host}}/pricing\n```json\n".to_string()
+                    + "{\"currency\":\"EUR\",\"labels\":{},\"x-a\":\"note\","
+                    + "\"additionalProp1\":{\"amount\":9.99},\"additionalProp2\":{\"amount\":9.99}}"
+                    + "\n```\n",
+                filename: "setPricing".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_enum_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["updateLitterStatus".to_string()]),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_litters_{litterId}".to_string(),
+            vec![HurlFileString {
+                file: "PATCH {{host}}/litters/7?notify=sms\n```json\n".to_string()
+                    + "{\"status\":\"available\",\"priority\":1}"
+                    + "\n```\n",
+                filename: "updateLitterStatus".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_entry_per_enum_value() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["updateLitterStatus".to_string()]),
+                formatting: Formatting::NoFormatting,
+                enum_property: Some("status".to_string()),
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let entry = |status: &str| {
+            format!("PATCH {{{{host}}}}/litters/7?notify=sms\n```json\n{{\"status\":\"{status}\",\"priority\":1}}\n```\n")
+        };
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_litters_{litterId}".to_string(),
+            vec![HurlFileString {
+                file: [entry("available"), entry("reserved"), entry("sold")].join("\n"),
+                filename: "updateLitterStatus".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_random_data_are_reproducible() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+        let settings = |sample_values| Settings {
+            input: Some(spec_path.clone()),
+            sample_values,
+            ..Settings::default()
+        };
+
+        let random = hurl_files_from_spec_path(&settings(SampleValues::random(3)), &spec).unwrap();
+
+        assert_eq!(
+            random,
+            hurl_files_from_spec_path(&settings(SampleValues::random(3)), &spec).unwrap()
+        );
+        assert_ne!(
+            random,
+            hurl_files_from_spec_path(&settings(SampleValues::random(4)), &spec).unwrap()
+        );
+        assert_ne!(
+            random,
+            hurl_files_from_spec_path(&settings(SampleValues::default()), &spec).unwrap()
+        );
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_dynamic_values() {
+        let spec_path = PathBuf::from_str("test_files/generated_values.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                operation_id_selection: Some(vec!["bookVisit".to_string()]),
+                sample_values: SampleValues::default().with_dynamic_values(true),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_owners_{ownerId}_visits".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/owners/{{newUuid}}/visits?since=2024-01-01T12%3A00%3A00Z\n"
+                    .to_string()
+                    + "Idempotency-Key: {{newUuid}}\n"
+                    + "```json\n"
+                    + "{\"id\":\"{{newUuid}}\",\"date\":\"2024-01-01\","
+                    + "\"email\":\"user-{{newUuid}}@example.com\","
+                    + "\"website\":\"https://example.com/{{newUuid}}\",\"clinicIp\":\"192.0.2.1\","
+                    + "\"clinicIpv6\":\"2001:db8::1\",\"clinicHost\":\"{{newUuid}}.example.com\","
+                    + "\"notes\":\"c3RyaW5n\",\"petIds\":[\"{{newUuid}}\",\"{{newUuid}}\"]}"
+                    + "\n```\n",
+                filename: "bookVisit".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_discriminator() {
+        let spec_path = PathBuf::from_str("test_files/polymorphic.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets\n```json\n{\"petType\":\"cat\",\"indoor\":true}\n```\n"
+                    .to_string(),
+                filename: "addPet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_entry_per_union_variant() {
+        let spec_path = PathBuf::from_str("test_files/polymorphic.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                union_variants: UnionVariantChoice::All,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets\n```json\n{\"petType\":\"cat\",\"indoor\":true}\n```\n"
+                    .to_string()
+                    + "\nPOST {{host}}/pets\n```json\n{\"petType\":\"Dog\",\"breed\":\"beagle\"}\n```\n",
+                filename: "addPet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_recursive_schema() {
+        let spec_path = PathBuf::from_str("test_files/recursive.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                validate_response: ResponseValidationChoice::Body,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![
+            (
+                "_categories".to_string(),
+                vec![HurlFileString {
+                    file: "POST {{host}}/categories\n```json\n".to_string()
+                        + "{\"name\":\"dogs\",\"parent\":null,\"children\":[]}"
+                        + "\n```\n\nHTTP 201"
+                        + "\n[Asserts]\n"
+                        + "\nheader \"Content-Type\" startsWith \"application/json\""
+                        + "\njsonpath \"$\" isCollection"
+                        + "\njsonpath \"$.name\" isString"
+                        + "\njsonpath \"$.children\" isCollection",
+                    filename: "addCategory".to_string(),
+                }],
+            ),
+            (
+                "_categories_search".to_string(),
+                vec![HurlFileString {
+                    file: "POST {{host}}/categories/search\n[FormParams]\nname: dogs\n\nHTTP 200\n"
+                        .to_string(),
+                    filename: "searchCategories".to_string(),
+                }],
+            ),
+        ];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_nested_allof() {
+        let spec_path = PathBuf::from_str("test_files/composition.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                validate_response: ResponseValidationChoice::Body,
+                body_fields: BodyFieldChoice::Required,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_dogs".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/dogs\n```json\n".to_string()
+                    + "{\"name\":\"Rex\",\"nickname\":\"string\",\"age\":3,\"breed\":\"collie\"}"
+                    + "\n```\n\nHTTP 201"
+                    + "\n[Asserts]\n"
+                    + "\nheader \"Content-Type\" startsWith \"application/json\""
+                    + "\njsonpath \"$\" isCollection"
+                    + "\njsonpath \"$.name\" isString"
+                    + "\njsonpath \"$.age\" isInteger"
+                    + "\njsonpath \"$.age\" >= 1"
+                    + "\njsonpath \"$.age\" <= 30"
+                    + "\njsonpath \"$.breed\" isString",
+                filename: "addDog".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_json_schema_keywords() {
+        let spec_path = PathBuf::from_str("test_files/json_schema_keywords.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                validate_response: ResponseValidationChoice::Body,
+                body_fields: BodyFieldChoice::Required,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_shipments".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/shipments\n```json\n".to_string()
+                    + "{\"kind\":\"parcel\",\"coordinates\":[52.5,13.4],\"tags\":[\"tracked\",\"fragile\"],"
+                    + "\"insured\":true,\"value\":100,\"status\":\"shipped\",\"express\":true,"
+                    + "\"deadline\":\"2024-01-01\"}"
+                    + "\n```\n\nHTTP 201"
+                    + "\n[Asserts]\n"
+                    + "\nheader \"Content-Type\" startsWith \"application/json\""
+                    + "\njsonpath \"$\" isCollection"
+                    + "\njsonpath \"$.kind\" == \"parcel\""
+                    + "\njsonpath \"$.coordinates\" isCollection"
+                    + "\njsonpath \"$.coordinates\" count >= 2"
+                    + "\njsonpath \"$.coordinates[0]\" isFloat"
+                    + "\njsonpath \"$.coordinates[1]\" isFloat"
+                    + "\njsonpath \"$.tags\" isCollection"
+                    + "\njsonpath \"$.tags\" includes \"tracked\""
+                    + "\njsonpath \"$.tags\" count >= 2"
+                    + "\njsonpath \"$.insured\" isBoolean"
+                    + "\njsonpath \"$.status\" isString"
+                    + "\njsonpath \"$.express\" isBoolean",
+                filename: "addShipment".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_nullable_properties() {
+        let spec_path = PathBuf::from_str("test_files/nullable.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_owners".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/owners\n```json\n".to_string()
+                    + "{\"name\":\"string\",\"age\":3,\"nickname\":\"string\",\"pet\":{\"name\":\"Rex\"}}"
+                    + "\n```\n",
+                filename: "addOwner".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_extra_null_values() {
+        let spec_path = PathBuf::from_str("test_files/nullable.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                null_values: NullValueChoice::Extra,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_owners".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/owners\n```json\n".to_string()
+                    + "{\"name\":\"string\",\"age\":3,\"nickname\":\"string\",\"pet\":{\"name\":\"Rex\"}}"
+                    + "\n```\n\nPOST {{host}}/owners\n```json\n"
+                    + "{\"name\":null,\"age\":3,\"nickname\":null,\"pet\":null}"
+                    + "\n```\n",
+                filename: "addOwner".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_all_response_codes() {
+        let spec_path = PathBuf::from_str("test_files/response_codes.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                custom_variables: CustomVariables {
+                    headers: vec![("Authorization".to_string(), "Bearer test".to_string())],
+                },
+                validate_response: ResponseValidationChoice::NonErrorCode,
+                response_code: ResponseCodeChoice::All,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> =
+            vec![
+                (
+                    "_pets".to_string(),
+                    vec![HurlFileString {
+                        file: "GET {{host}}/pets\nAuthorization: {{Authorization}}\n\nHTTP 200\n\n"
+                            .to_string()
+                            + "GET {{host}}/pets\n\nHTTP 401\n",
+                        filename: "listPets".to_string(),
+                    }, HurlFileString {
+                        file: "POST {{host}}/pets\nAuthorization: {{Authorization}}\n```json\n"
+                            .to_string()
+                            + "{\"name\":\"Rex\",\"tag\":\"good\"}\n```\n\nHTTP 201\n\n"
+                            + "POST {{host}}/pets\nAuthorization: {{Authorization}}\n```json\n"
+                            + "{\"tag\":\"good\"}\n```\n\nHTTP 422\n",
+                        filename: "createPet".to_string(),
+                    }],
+                ),
+                (
+                    "_pets_{petId}".to_string(),
+                    vec![HurlFileString {
+                    file: "GET {{host}}/pets/7\nAuthorization: {{Authorization}}\n\nHTTP 200\n\n"
+                        .to_string()
+                        + "GET {{host}}/pets/7\n\nHTTP 401\n\n"
+                        + "GET {{host}}/pets/999999999\nAuthorization: {{Authorization}}\n"
+                        + "\nHTTP 404\n",
+                    filename: "showPetById".to_string(),
+                }, HurlFileString {
+                    file: "PATCH {{host}}/pets/7\nAuthorization: {{Authorization}}\n```json\n"
+                        .to_string()
+                        + "{\"tag\":\"good\"}\n```\n\nHTTP 200\n",
+                    filename: "updatePet".to_string(),
+                }],
+                ),
+            ];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_without_entries_for_responses_the_request_cant_trigger() {
+        let spec_path = PathBuf::from_str("test_files/response_codes.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path.clone()),
+                formatting: Formatting::NoFormatting,
+                operation_id_selection: Some(vec!["listPets".to_string()]),
+                validate_response: ResponseValidationChoice::NonErrorCode,
+                response_code: ResponseCodeChoice::All,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "GET {{host}}/pets\n\nHTTP 200\n".to_string(),
+                filename: "listPets".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                operation_id_selection: Some(vec!["updatePet".to_string()]),
+                validate_response: ResponseValidationChoice::NonErrorCode,
+                response_code: ResponseCodeChoice::Code("404".to_string()),
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets_{petId}".to_string(),
+            vec![HurlFileString {
+                file: "PATCH {{host}}/pets/7\n```json\n{\"tag\":\"good\"}\n```\n".to_string(),
+                filename: "updatePet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_response_headers() {
+        let spec_path = PathBuf::from_str("test_files/response_codes.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                operation_id_selection: Some(vec!["createPet".to_string()]),
+                validate_response: ResponseValidationChoice::BodyWithOptionals,
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_pets".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/pets\n```json\n{\"name\":\"Rex\",\"tag\":\"good\"}\n```\n"
+                    .to_string()
+                    + "\nHTTP 201\n[Asserts]\n"
+                    + "\nheader \"Location\" exists"
+                    + "\nheader \"Location\" matches /^\\/pets\\/\\d+$/"
+                    + "\nheader \"X-RateLimit-Remaining\" exists"
+                    + "\nheader \"X-RateLimit-Remaining\" matches /^-?\\d+$/"
+                    + "\nheader \"X-RateLimit-Remaining\" toInt >= 0"
+                    + "\nheader \"ETag\" exists"
+                    + "\nheader \"Cache-Control\" exists"
+                    + "\nheader \"Cache-Control\" matches /^(no\\-store|no\\-cache)$/"
+                    + "\nheader \"Content-Type\" startsWith \"application/json\""
+                    + "\njsonpath \"$\" isCollection"
+                    + "\njsonpath \"$.name\" isString"
+                    + "\njsonpath \"$.tag\" isString",
+                filename: "createPet".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
+
+    #[test]
+    fn hurl_files_from_spec_with_max_depth() {
+        let spec_path = PathBuf::from_str("test_files/recursive.json").unwrap();
+        let spec = spec_reader::from_path(spec_path.clone()).unwrap();
+
+        let result = hurl_files_from_spec_path(
+            &Settings {
+                input: Some(spec_path),
+                formatting: Formatting::NoFormatting,
+                operation_id_selection: Some(vec!["addCategory".to_string()]),
+                max_depth: Some(1),
+                media_type_headers: false,
+                ..Settings::default()
+            },
+            &spec,
+        );
+
+        let expected: Vec<(String, Vec<HurlFileString>)> = vec![(
+            "_categories".to_string(),
+            vec![HurlFileString {
+                file: "POST {{host}}/categories\n```json\n".to_string()
+                    + "{\"name\":null,\"parent\":null,\"children\":[]}"
+                    + "\n```\n",
+                filename: "addCategory".to_string(),
+            }],
+        )];
+        assert_eq!(expected, result.unwrap());
+    }
 }
@@ -0,0 +1,563 @@
+use std::collections::BTreeMap;
+
+use oas3::Schema;
+use serde_json::{Map, Value};
+
+/// The specification as it was written. `oas3` doesn't model every keyword of a schema (e.g.
+/// `xml`), keywords it drops can be read from here instead.
+#[derive(Debug, Default)]
+pub struct RawSpec {
+    document: Value,
+}
+
+impl RawSpec {
+    pub fn from_str(contents: &str) -> Result<Self, serde_yml::Error> {
+        Ok(Self {
+            document: serde_yml::from_str(contents)?,
+        })
+    }
+
+    pub fn root(&self) -> RawNode<'_> {
+        RawNode {
+            document: &self.document,
+            value: Some(&self.document),
+        }
+    }
+}
+
+/// A node of the raw specification, references are followed when navigating to a child.
+/// A node that doesn't exist in the document is empty, so it can still be navigated.
+#[derive(Clone, Copy)]
+pub struct RawNode<'a> {
+    document: &'a Value,
+    value: Option<&'a Value>,
+}
+
+impl<'a> RawNode<'a> {
+    pub fn child(&self, key: &str) -> RawNode<'a> {
+        let value = self.value.and_then(|v| match v {
+            Value::Array(items) => key.parse::<usize>().ok().and_then(|i| items.get(i)),
+            v => v.get(key),
+        });
+
+        RawNode {
+            document: self.document,
+            value: value.and_then(|v| self.follow_refs(v)),
+        }
+    }
+
+    /// A property of the schema, including properties declared in `allOf` subschemas.
+    pub fn property(&self, name: &str) -> RawNode<'a> {
+        let property = self.child("properties").child(name);
+        if property.value.is_some() {
+            return property;
+        }
+
+        self.child("allOf")
+            .elements()
+            .into_iter()
+            .map(|subschema| subschema.property(name))
+            .find(|p| p.value.is_some())
+            .unwrap_or(property)
+    }
+
+    /// The names of the schema's properties in the order they're declared. Properties declared
+    /// in `allOf` subschemas come first, in the order of the subschemas.
+    pub fn property_names(&self) -> Vec<&'a str> {
+        let mut names: Vec<&'a str> = vec![];
+        let inherited = self
+            .child("allOf")
+            .elements()
+            .into_iter()
+            .flat_map(|subschema| subschema.property_names());
+        let declared = self.child("properties").entries().into_iter().map(|p| p.0);
+
+        for name in inherited.chain(declared) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+
+        names
+    }
+
+    pub fn items(&self) -> RawNode<'a> {
+        self.child("items")
+    }
+
+    pub fn get(&self, keyword: &str) -> Option<&'a Value> {
+        self.value.and_then(|v| v.get(keyword))
+    }
+
+    /// The keys and values of an object node.
+    pub fn entries(&self) -> Vec<(&'a str, RawNode<'a>)> {
+        match self.value.and_then(|v| v.as_object()) {
+            Some(entries) => entries
+                .iter()
+                .map(|(key, value)| {
+                    (
+                        key.as_str(),
+                        RawNode {
+                            document: self.document,
+                            value: self.follow_refs(value),
+                        },
+                    )
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The elements of an array node.
+    pub fn elements(&self) -> Vec<RawNode<'a>> {
+        match self.value.and_then(|v| v.as_array()) {
+            Some(elements) => elements
+                .iter()
+                .map(|e| RawNode {
+                    document: self.document,
+                    value: self.follow_refs(e),
+                })
+                .collect(),
+            None => vec![],
+        }
+    }
+
+    /// The schema with its `allOf` subschemas merged into it, like `merge_all_of` merges the
+    /// parsed schema, so keywords the merged schema got from a subschema can be read. Keywords of
+    /// the schema take precedence, `properties` and `required` are combined.
+    pub fn merged_all_of(&self) -> Option<Value> {
+        self.merge_subschemas(&mut vec![])
+    }
+
+    /// A node of a value that isn't part of the document, e.g. a merged schema. References in
+    /// the value are still followed into the document.
+    pub fn with_value<'b>(&self, value: &'b Value) -> RawNode<'b>
+    where
+        'a: 'b,
+    {
+        RawNode {
+            document: self.document,
+            value: Some(value),
+        }
+    }
+
+    fn merge_subschemas(&self, merged_refs: &mut Vec<&'a str>) -> Option<Value> {
+        let mut schema = self.value?.as_object()?.clone();
+        let mut merged = Map::new();
+
+        if let Some(Value::Array(subschemas)) = self.value.and_then(|v| v.get("allOf")) {
+            for subschema in subschemas {
+                if let Some(ref_path) = subschema.get("$ref").and_then(|r| r.as_str()) {
+                    if merged_refs.contains(&ref_path) {
+                        continue;
+                    }
+                    merged_refs.push(ref_path);
+                }
+
+                let subschema = RawNode {
+                    document: self.document,
+                    value: self.follow_refs(subschema),
+                };
+                if let Some(Value::Object(resolved)) = subschema.merge_subschemas(merged_refs) {
+                    merge_keywords(&mut merged, resolved);
+                }
+            }
+        }
+
+        schema.remove("allOf");
+        merge_keywords(&mut merged, schema);
+
+        Some(Value::Object(merged))
+    }
+
+    /// Parses the node as a schema, for keywords `oas3` drops that hold a schema.
+    pub fn schema(&self) -> Option<Schema> {
+        let mut value = self.value?.clone();
+        make_oas3_compatible(&mut value);
+
+        serde_json::from_value(value).ok()
+    }
+
+    /// The first element of an array node matching the predicate, an empty node if there's none.
+    pub fn find(&self, predicate: impl Fn(&RawNode<'a>) -> bool) -> RawNode<'a> {
+        self.elements()
+            .into_iter()
+            .find(|e| predicate(e))
+            .unwrap_or(RawNode {
+                document: self.document,
+                value: None,
+            })
+    }
+
+    /// Whether the value matches the schema's `const`, `enum`, `type`, `required` and
+    /// `properties` keywords. Other keywords aren't checked, an empty node matches anything.
+    pub fn accepts(&self, value: &Value) -> bool {
+        if self.get("const").is_some_and(|c| c != value) {
+            return false;
+        }
+
+        if self
+            .get("enum")
+            .and_then(|e| e.as_array())
+            .is_some_and(|values| !values.contains(value))
+        {
+            return false;
+        }
+
+        let accepts_type = |t: &Value| match t.as_str() {
+            Some("null") => value.is_null(),
+            Some("boolean") => value.is_boolean(),
+            Some("integer") => value.is_i64() || value.is_u64(),
+            Some("number") => value.is_number(),
+            Some("string") => value.is_string(),
+            Some("array") => value.is_array(),
+            Some("object") => value.is_object(),
+            _ => true,
+        };
+        let accepts_types = match self.get("type") {
+            Some(Value::Array(types)) => types.iter().any(accepts_type),
+            Some(t) => accepts_type(t),
+            None => true,
+        };
+        if !accepts_types {
+            return false;
+        }
+
+        match value.as_object() {
+            Some(object) => {
+                let has_required =
+                    self.get("required")
+                        .and_then(|r| r.as_array())
+                        .is_none_or(|names| {
+                            names
+                                .iter()
+                                .all(|n| n.as_str().is_some_and(|n| object.contains_key(n)))
+                        });
+
+                has_required
+                    && object.iter().all(|(name, value)| {
+                        let property = self.child("properties").child(name);
+                        property.value.is_none() || property.accepts(value)
+                    })
+            }
+            None => true,
+        }
+    }
+
+    fn follow_refs(&self, value: &'a Value) -> Option<&'a Value> {
+        let mut value = value;
+        // Limit the number of jumps so a reference to itself can't loop forever
+        for _ in 0..32 {
+            match value.get("$ref").and_then(|r| r.as_str()) {
+                Some(ref_path) => match ref_path.strip_prefix('#') {
+                    Some(pointer) => value = self.document.pointer(pointer)?,
+                    None => return None,
+                },
+                None => return Some(value),
+            }
+        }
+        None
+    }
+}
+
+fn merge_keywords(base: &mut Map<String, Value>, schema: Map<String, Value>) {
+    for (keyword, value) in schema {
+        match (keyword.as_str(), base.get_mut(&keyword), value) {
+            (
+                "properties" | "patternProperties",
+                Some(Value::Object(base)),
+                Value::Object(value),
+            ) => {
+                base.extend(value);
+            }
+            ("required", Some(Value::Array(base)), Value::Array(value)) => {
+                for name in value {
+                    if !base.contains(&name) {
+                        base.push(name);
+                    }
+                }
+            }
+            (_, _, value) => {
+                base.insert(keyword, value);
+            }
+        }
+    }
+}
+
+/// The properties `oas3` parsed, in the order they're declared in the raw schema. Properties
+/// the raw schema doesn't declare follow in alphabetical order.
+pub fn ordered_properties<'s, T>(
+    properties: &'s BTreeMap<String, T>,
+    raw: RawNode,
+) -> Vec<(&'s String, &'s T)> {
+    ordered_entries(properties, &raw.property_names())
+}
+
+/// The entries `oas3` parsed, in the order of the names. Entries not named follow in
+/// alphabetical order.
+pub fn ordered_entries<'s, T>(
+    entries: &'s BTreeMap<String, T>,
+    names: &[&str],
+) -> Vec<(&'s String, &'s T)> {
+    let mut ordered: Vec<(&String, &T)> = names
+        .iter()
+        .filter_map(|name| entries.get_key_value(*name))
+        .collect();
+    ordered.extend(entries.iter().filter(|e| !names.contains(&e.0.as_str())));
+
+    ordered
+}
+
+/// Keywords whose values are instances rather than schemas, they're never rewritten.
+const VALUE_KEYWORDS: [&str; 4] = ["example", "examples", "default", "const"];
+/// Keywords of objects mapping names to schemas or other objects, e.g. a property named
+/// `default` is still rewritten.
+const NAME_MAPS: [&str; 13] = [
+    "properties",
+    "patternProperties",
+    "dependentSchemas",
+    "$defs",
+    "schemas",
+    "responses",
+    "parameters",
+    "requestBodies",
+    "headers",
+    "encoding",
+    "callbacks",
+    "pathItems",
+    "links",
+];
+
+/// Rewrites valid keywords `oas3` can't parse into a form it can. A boolean
+/// `additionalProperties` is replaced with an empty schema when it's true and removed otherwise,
+/// `enum` values that aren't strings are converted to strings. Values of `example`, `examples`,
+/// `default` and `const` are left as written. The raw specification still has them as written.
+pub fn make_oas3_compatible(value: &mut Value) {
+    rewrite_keywords(value, false)
+}
+
+/// Rewrites the keywords of the value, or of the values it maps names to.
+fn rewrite_keywords(value: &mut Value, is_name_map: bool) {
+    match value {
+        Value::Object(object) if is_name_map => object
+            .values_mut()
+            .for_each(|value| rewrite_keywords(value, false)),
+        Value::Object(object) => {
+            if let Some(Value::Array(values)) = object.get_mut("enum") {
+                for value in values.iter_mut().filter(|v| !v.is_string()) {
+                    *value = Value::String(value.to_string());
+                }
+            }
+
+            match object.get("additionalProperties") {
+                Some(Value::Bool(true)) => {
+                    object.insert(
+                        "additionalProperties".to_string(),
+                        Value::Object(Default::default()),
+                    );
+                }
+                Some(Value::Bool(false)) => {
+                    object.remove("additionalProperties");
+                }
+                _ => (),
+            }
+            object
+                .iter_mut()
+                .filter(|(keyword, _)| !VALUE_KEYWORDS.contains(&keyword.as_str()))
+                .for_each(|(keyword, value)| {
+                    rewrite_keywords(value, NAME_MAPS.contains(&keyword.as_str()))
+                });
+        }
+        Value::Array(items) => items
+            .iter_mut()
+            .for_each(|value| rewrite_keywords(value, false)),
+        _ => (),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{make_oas3_compatible, ordered_properties, RawSpec};
+
+    #[test]
+    fn raw_node_child_follows_references() {
+        let raw = RawSpec::from_str(
+            r##"{
+                "paths": {"/pets": {"post": {"requestBody": {"$ref": "#/components/requestBodies/Pet"}}}},
+                "components": {
+                    "requestBodies": {"Pet": {"content": {"application/xml": {"schema": {"$ref": "#/components/schemas/Pet"}}}}},
+                    "schemas": {"Pet": {"xml": {"name": "pet"}}}
+                }
+            }"##,
+        )
+        .unwrap();
+
+        let schema = raw
+            .root()
+            .child("paths")
+            .child("/pets")
+            .child("post")
+            .child("requestBody")
+            .child("content")
+            .child("application/xml")
+            .child("schema");
+
+        assert_eq!(Some(&json!({"name": "pet"})), schema.get("xml"));
+    }
+
+    #[test]
+    fn raw_node_child_of_missing_node_is_empty() {
+        let raw = RawSpec::from_str("openapi: 3.1.0").unwrap();
+
+        assert_eq!(None, raw.root().property("missing").items().get("xml"));
+    }
+
+    #[test]
+    fn raw_node_accepts_values_matching_the_schema() {
+        let raw = RawSpec::from_str(
+            r##"{
+                "type": "object",
+                "required": ["kind"],
+                "properties": {
+                    "kind": {"const": "dog"},
+                    "size": {"type": ["integer", "null"], "enum": [1, 2, null]}
+                }
+            }"##,
+        )
+        .unwrap();
+
+        assert_eq!(true, raw.root().accepts(&json!({"kind": "dog", "size": 2})));
+        assert_eq!(
+            true,
+            raw.root().accepts(&json!({"kind": "dog", "size": null}))
+        );
+        assert_eq!(false, raw.root().accepts(&json!({"kind": "cat"})));
+        assert_eq!(
+            false,
+            raw.root().accepts(&json!({"kind": "dog", "size": 3}))
+        );
+        assert_eq!(false, raw.root().accepts(&json!({"size": 1})));
+        assert_eq!(false, raw.root().accepts(&json!("dog")));
+        assert_eq!(
+            true,
+            RawSpec::default().root().child("if").accepts(&json!(1))
+        );
+    }
+
+    #[test]
+    fn make_oas3_compatible_rewrites_unparsable_keywords() {
+        let mut value = json!({
+            "properties": {
+                "labels": {"additionalProperties": true},
+                "strict": {"additionalProperties": false, "type": "object"},
+                "size": {"enum": [1, "2", null]},
+                "default": {"enum": [1]},
+                "meta": {
+                    "default": {"enum": [1], "additionalProperties": false},
+                    "examples": [{"additionalProperties": true}],
+                    "const": {"enum": [1]}
+                }
+            }
+        });
+
+        make_oas3_compatible(&mut value);
+
+        assert_eq!(
+            json!({
+                "properties": {
+                    "labels": {"additionalProperties": {}},
+                    "strict": {"type": "object"},
+                    "size": {"enum": ["1", "2", "null"]},
+                    "default": {"enum": ["1"]},
+                    "meta": {
+                        "default": {"enum": [1], "additionalProperties": false},
+                        "examples": [{"additionalProperties": true}],
+                        "const": {"enum": [1]}
+                    }
+                }
+            }),
+            value
+        );
+    }
+
+    #[test]
+    fn raw_node_merged_all_of_has_keywords_of_subschemas() {
+        let raw_spec = RawSpec::from_str(
+            r##"{
+                "components": {"schemas": {
+                    "Named": {
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string"}},
+                        "discriminator": {"propertyName": "name"},
+                        "allOf": [{"$ref": "#/components/schemas/Named"}]
+                    },
+                    "Pet": {
+                        "allOf": [
+                            {"$ref": "#/components/schemas/Named"},
+                            {"required": ["age"], "properties": {"age": {"type": "integer"}}}
+                        ],
+                        "properties": {"name": {"const": "Rex"}},
+                        "prefixItems": [{"type": "string"}]
+                    }
+                }}
+            }"##,
+        )
+        .unwrap();
+        let pet = raw_spec
+            .root()
+            .child("components")
+            .child("schemas")
+            .child("Pet");
+
+        let merged = pet.merged_all_of().unwrap();
+        assert_eq!(
+            json!({
+                "required": ["name", "age"],
+                "properties": {"name": {"const": "Rex"}, "age": {"type": "integer"}},
+                "discriminator": {"propertyName": "name"},
+                "prefixItems": [{"type": "string"}]
+            }),
+            merged
+        );
+        assert_eq!(
+            Some(&json!("name")),
+            pet.with_value(&merged)
+                .child("discriminator")
+                .get("propertyName")
+        );
+    }
+
+    #[test]
+    fn ordered_properties_follow_declaration_order() {
+        let raw = RawSpec::from_str(
+            r##"{
+                "allOf": [{"$ref": "#/components/schemas/Base"}],
+                "properties": {"name": {}, "id": {}},
+                "components": {"schemas": {"Base": {"properties": {"kind": {"xml": {"name": "type"}}}}}}
+            }"##,
+        )
+        .unwrap();
+        let properties = BTreeMap::from([
+            ("id".to_string(), 1),
+            ("kind".to_string(), 2),
+            ("name".to_string(), 3),
+            ("extra".to_string(), 4),
+        ]);
+
+        let names: Vec<&str> = ordered_properties(&properties, raw.root())
+            .into_iter()
+            .map(|p| p.0.as_str())
+            .collect();
+
+        assert_eq!(vec!["kind", "name", "id", "extra"], names);
+        assert_eq!(
+            Some(&json!({"name": "type"})),
+            raw.root().property("kind").get("xml")
+        );
+    }
+}
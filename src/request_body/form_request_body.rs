@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+
+use hurl_core::ast::KeyValue;
+use log::debug;
+use oas3::{
+    spec::{Encoding, RefError},
+    Schema, Spec,
+};
+
+use super::{
+    json_request_body::parse_json_from_schema,
+    request_body::{param_value_from_json, SpecBodySettings},
+};
+use crate::{
+    custom_hurl_ast::key_value,
+    raw_spec::{ordered_properties, RawNode},
+    schema_ancestors::SchemaAncestors,
+};
+
+const FORM_STYLE: &str = "form";
+const SPACE_DELIMITED_STYLE: &str = "spaceDelimited";
+const PIPE_DELIMITED_STYLE: &str = "pipeDelimited";
+const DEEP_OBJECT_STYLE: &str = "deepObject";
+
+pub fn parse_form_params_from_schema(
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    encoding: &BTreeMap<String, Encoding>,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Vec<KeyValue>, RefError> {
+    let mut params = vec![];
+
+    for prop in ordered_properties(&schema.properties, raw) {
+        let prop_schema = prop.1.resolve(spec)?;
+        if prop_schema.read_only.unwrap_or(false) {
+            continue;
+        }
+
+        if ancestors.stops_at(prop.1) {
+            debug!(
+                "Leaving out the param {}, it's recursive or nested deeper than {} schemas",
+                prop.0,
+                ancestors.max_depth()
+            );
+            continue;
+        }
+
+        let value = match parse_json_from_schema(
+            prop_schema,
+            raw.property(prop.0),
+            &ancestors.child(prop.1),
+            spec,
+            settings,
+        )? {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let default_encoding = Encoding::default();
+        let prop_encoding = encoding.get(prop.0).unwrap_or(&default_encoding);
+
+        params.extend(
+            form_values(prop.0, &value, prop_encoding)
+                .into_iter()
+                .map(|kv| key_value(kv.0, kv.1)),
+        );
+    }
+
+    Ok(params)
+}
+
+/// Serializes a value following the `style` and `explode` rules of the encoding object.
+/// See https://spec.openapis.org/oas/v3.1.0#style-values
+fn form_values(
+    name: &str,
+    value: &serde_json::Value,
+    encoding: &Encoding,
+) -> Vec<(String, String)> {
+    // An explicit content type is only used when no style is given.
+    if encoding.style.is_none()
+        && encoding
+            .content_type
+            .as_ref()
+            .is_some_and(|ct| ct.contains("json"))
+    {
+        return vec![(name.to_string(), value.to_string())];
+    }
+
+    let style = encoding.style.as_deref().unwrap_or(FORM_STYLE);
+    let explode = encoding.explode.unwrap_or(style == FORM_STYLE);
+
+    match value {
+        serde_json::Value::Array(items) => {
+            let values = items.iter().map(param_value_from_json);
+
+            match style {
+                FORM_STYLE if explode => values.map(|v| (name.to_string(), v)).collect(),
+                SPACE_DELIMITED_STYLE => {
+                    vec![(name.to_string(), values.collect::<Vec<String>>().join(" "))]
+                }
+                PIPE_DELIMITED_STYLE => {
+                    vec![(name.to_string(), values.collect::<Vec<String>>().join("|"))]
+                }
+                _ => vec![(name.to_string(), values.collect::<Vec<String>>().join(","))],
+            }
+        }
+        serde_json::Value::Object(props) => match style {
+            DEEP_OBJECT_STYLE => props
+                .iter()
+                .map(|p| (format!("{name}[{}]", p.0), param_value_from_json(p.1)))
+                .collect(),
+            FORM_STYLE if explode => props
+                .iter()
+                .map(|p| (p.0.to_string(), param_value_from_json(p.1)))
+                .collect(),
+            _ => vec![(
+                name.to_string(),
+                props
+                    .iter()
+                    .flat_map(|p| [p.0.to_string(), param_value_from_json(p.1)])
+                    .collect::<Vec<String>>()
+                    .join(","),
+            )],
+        },
+        v => vec![(name.to_string(), param_value_from_json(v))],
+    }
+}
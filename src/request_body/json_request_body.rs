@@ -1,51 +1,255 @@
-use log::debug;
+use log::{debug, warn};
 use oas3::{
-    spec::{ObjectOrReference, RefError},
+    spec::{ObjectOrReference, RefError, SchemaType, SchemaTypeSet},
     Schema, Spec,
 };
 use serde_json::{Map, Number};
 
 use super::request_body::SpecBodySettings;
+use crate::cli::{BodyFieldChoice, NullValueChoice, UnionVariantChoice};
+use crate::{
+    raw_spec::{ordered_properties, RawNode},
+    sample_values::values::{declared_value, NEW_UUID},
+    schema_ancestors::SchemaAncestors,
+    schema_composition::merge_all_of,
+};
+
+/// Keys of generated additional properties are numbered from this prefix, e.g. `additionalProp1`.
+const ADDITIONAL_PROPERTY_KEY: &str = "additionalProp";
+/// Used if no key matching a `patternProperties` pattern could be generated.
+const PATTERN_PROPERTY_KEY: &str = "key";
+/// How many items with random values are generated to find one that isn't in an array yet.
+const UNIQUE_ITEM_ATTEMPTS: u64 = 10;
+
+/// Generates the request bodies of the schema. If an enum property is chosen there are bodies
+/// for every value of its enum, otherwise the bodies of the variants. If explicit nulls are
+/// chosen there's an extra body with the nullable properties set to null.
+pub fn parse_json_bodies_from_schema(
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Vec<serde_json::Value>, RefError> {
+    let enum_values = match &settings.enum_property {
+        Some(name) => property_enum_values(raw, name, settings.max_depth),
+        None => vec![],
+    };
+
+    let mut bodies = vec![];
+    if enum_values.is_empty() {
+        if let Some(name) = &settings.enum_property {
+            warn!(
+                "The request body has no property {name} with an enum. Generating a single entry"
+            );
+        }
+        bodies = parse_json_variants_from_schema(schema.clone(), raw, ancestors, spec, settings)?;
+    }
+
+    for value in enum_values {
+        let settings = SpecBodySettings {
+            enum_value: Some(value),
+            ..settings.clone()
+        };
+        bodies.extend(parse_json_variants_from_schema(
+            schema.clone(),
+            raw,
+            ancestors,
+            spec,
+            &settings,
+        )?);
+    }
+
+    if matches!(settings.null_values, NullValueChoice::Extra) {
+        let settings = SpecBodySettings {
+            explicit_nulls: true,
+            ..settings.clone()
+        };
+        match parse_json_from_schema(schema, raw, ancestors, spec, &settings)? {
+            Some(body) if !bodies.contains(&body) => bodies.push(body),
+            _ => debug!(
+                "The request body has no nullable properties, not generating an entry with nulls"
+            ),
+        }
+    }
+
+    Ok(bodies)
+}
+
+/// The enum values of the first property with the name that's nested in the schema.
+fn property_enum_values(raw: RawNode, name: &str, max_depth: usize) -> Vec<serde_json::Value> {
+    if max_depth == 0 {
+        return vec![];
+    }
+
+    if let Some(serde_json::Value::Array(values)) = raw.property(name).get("enum") {
+        return values.clone();
+    }
+
+    let nested = raw
+        .child("properties")
+        .entries()
+        .into_iter()
+        .map(|(_, property)| property)
+        .chain([raw.items()])
+        .chain(
+            ["allOf", "oneOf", "anyOf"]
+                .iter()
+                .flat_map(|keyword| raw.child(keyword).elements()),
+        );
+
+    for schema in nested {
+        let values = property_enum_values(schema, name, max_depth - 1);
+        if !values.is_empty() {
+            return values;
+        }
+    }
 
+    vec![]
+}
+
+/// Generates one value per variant of the schema's oneOf or anyOf when all variants are chosen,
+/// otherwise a single value.
+pub fn parse_json_variants_from_schema(
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Vec<serde_json::Value>, RefError> {
+    let (variants, keyword) = match !schema.one_of.is_empty() {
+        true => (&schema.one_of, "oneOf"),
+        false => (&schema.any_of, "anyOf"),
+    };
+
+    // Anything that parse_json_from_schema checks before the variants takes precedence
+    let all_variants = matches!(settings.union_variants, UnionVariantChoice::All)
+        && variants.len() > 1
+        && !schema.read_only.unwrap_or(false)
+        && declared_value(&schema, raw).is_none()
+        && schema.all_of.is_empty();
+
+    if !all_variants {
+        return Ok(
+            parse_json_from_schema(schema, raw, ancestors, spec, settings)?
+                .into_iter()
+                .collect(),
+        );
+    }
+
+    let mut values = vec![];
+    for index in 0..variants.len() {
+        values.extend(json_obj_from_variant(
+            variants, index, raw, keyword, ancestors, spec, settings,
+        )?);
+    }
+
+    Ok(values)
+}
+
+/// Generates a value of the schema. If the value matches the schema's `not` a declared value
+/// that doesn't is used instead, see `value_not_matching`.
 pub fn parse_json_from_schema(
     schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
     spec: &Spec,
     settings: &SpecBodySettings,
 ) -> Result<Option<serde_json::Value>, RefError> {
-    if schema.read_only.unwrap_or(false) {
-        return Ok(None);
+    let value = json_value_from_schema(schema, raw, ancestors, spec, settings)?;
+
+    match value {
+        Some(v) if raw.get("not").is_some() && raw.child("not").accepts(&v) => {
+            Ok(Some(value_not_matching(v, raw)))
+        }
+        v => Ok(v),
     }
+}
+
+/// Picks a value of the schema's `enum` or `examples` that doesn't match its `not`. `oas3`
+/// drops `not`, so only keywords `RawNode::accepts` checks are excluded.
+fn value_not_matching(value: serde_json::Value, raw: RawNode) -> serde_json::Value {
+    let raw_not = raw.child("not");
+    let declared = ["enum", "examples"]
+        .iter()
+        .filter_map(|keyword| raw.get(keyword).and_then(|v| v.as_array()))
+        .flatten()
+        .find(|v| !raw_not.accepts(v));
 
-    match schema.example {
-        Some(ex) => return Ok(Some(ex)),
-        None => (),
+    match declared {
+        Some(v) => v.clone(),
+        None => {
+            warn!(
+                "Couldn't generate a value that doesn't match the schema in `not`, using {value}"
+            );
+            value
+        }
     }
+}
 
-    match schema.enum_values.first() {
-        Some(e) => return Ok(Some(serde_json::Value::String(e.to_string()))),
-        None => (),
+fn json_value_from_schema(
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Option<serde_json::Value>, RefError> {
+    if schema.read_only.unwrap_or(false) {
+        return Ok(None);
+    }
+
+    if let Some(value) = declared_value(&schema, raw) {
+        return Ok(Some(value));
     }
 
     if schema.all_of.len() > 0 {
-        return Ok(Some(json_obj_from_allof(schema, spec, settings)?));
+        let merged = merge_all_of(schema, spec)?;
+        let merged_raw = raw.merged_all_of();
+        let raw = match &merged_raw {
+            Some(value) => raw.with_value(value),
+            None => raw,
+        };
+        return json_value_from_schema(merged, raw, ancestors, spec, settings);
     }
 
     if schema.one_of.len() > 0 {
-        return Ok(json_obj_from_anyof(schema.one_of, spec, &settings)?);
+        let index = first_non_null_variant(&schema.one_of, spec)?;
+        return json_obj_from_variant(
+            &schema.one_of,
+            index,
+            raw,
+            "oneOf",
+            ancestors,
+            spec,
+            settings,
+        );
     }
 
     // Treat any_of and one_of the same / use only the first schema of both
     if schema.any_of.len() > 0 {
-        return Ok(json_obj_from_anyof(schema.any_of, spec, &settings)?);
+        let index = first_non_null_variant(&schema.any_of, spec)?;
+        return json_obj_from_variant(
+            &schema.any_of,
+            index,
+            raw,
+            "anyOf",
+            ancestors,
+            spec,
+            settings,
+        );
     }
 
     let default_val = match schema.schema_type {
         Some(ref typeset) => match typeset {
-            oas3::spec::SchemaTypeSet::Single(t) => Some(default_json_value_from_schema_type(*t)),
-            oas3::spec::SchemaTypeSet::Multiple(ts) => match ts.first() {
-                Some(t) => Some(default_json_value_from_schema_type(*t)),
-                None => None,
-            },
+            oas3::spec::SchemaTypeSet::Single(t) => {
+                Some(default_json_value_from_schema_type(*t, &schema, settings))
+            }
+            // A nullable schema gets a value of its non-null type, nulls are only set explicitly
+            oas3::spec::SchemaTypeSet::Multiple(ts) => ts
+                .iter()
+                .find(|t| **t != SchemaType::Null)
+                .or(ts.first())
+                .map(|t| default_json_value_from_schema_type(*t, &schema, settings)),
         },
         None => None,
     };
@@ -54,41 +258,22 @@ pub fn parse_json_from_schema(
         Some(v) => {
             return match v {
                 SimpleJsonValue::Scalar(s) => Ok(Some(s)),
-                SimpleJsonValue::Array => match schema.items {
-                    Some(items_schema) => {
-                        let schema = match items_schema.resolve(spec) {
-                            Ok(s) => parse_json_from_schema(s, spec, settings)?,
-                            Err(e) => return Err(e),
-                        };
-
-                        match schema {
-                            Some(s) => Ok(Some(serde_json::Value::Array(vec![s]))),
-                            None => Ok(Some(serde_json::Value::Array(vec![]))),
-                        }
-                    }
-                    None => Ok(Some(serde_json::Value::Array(vec![]))),
-                },
+                SimpleJsonValue::Array => {
+                    Ok(Some(json_array(&schema, raw, ancestors, spec, settings)?))
+                }
                 SimpleJsonValue::Object => {
-                    let props = parse_json_object(&schema, spec, settings)?;
+                    let props = parse_json_object(&schema, raw, ancestors, spec, settings)?;
 
                     Ok(Some(serde_json::Value::Object(props)))
                 }
             };
         }
         None => {
-            if schema.properties.len() > 0 {
-                let props = parse_json_object(&schema, spec, settings)?;
+            if !schema.properties.is_empty() || schema.additional_properties.is_some() {
+                let props = parse_json_object(&schema, raw, ancestors, spec, settings)?;
                 Ok(Some(serde_json::Value::Object(props)))
-            } else if schema.items.is_some() {
-                let items_schema = match schema.items.unwrap().resolve(spec) {
-                    Ok(s) => parse_json_from_schema(s, spec, settings)?,
-                    Err(e) => return Err(e),
-                };
-
-                match items_schema {
-                    Some(s) => Ok(Some(serde_json::Value::Array(vec![s]))),
-                    None => Ok(Some(serde_json::Value::Array(vec![]))),
-                }
+            } else if schema.items.is_some() || raw.get("prefixItems").is_some() {
+                Ok(Some(json_array(&schema, raw, ancestors, spec, settings)?))
             } else {
                 debug!("Couldn't build anything from schema. Returning null...");
 
@@ -98,87 +283,567 @@ pub fn parse_json_from_schema(
     }
 }
 
+/// Generates the `prefixItems` of a tuple, then `minContains` items matching `contains`, then
+/// enough items to satisfy `minItems` and `maxItems`. Items the walk stops at are left out.
+fn json_array(
+    schema: &Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<serde_json::Value, RefError> {
+    let mut items = vec![];
+
+    // `oas3` drops `prefixItems` and `contains`, their schemas are parsed from the raw spec
+    let inline_ancestors = ancestors.inline_child();
+    if !ancestors.is_at_max_depth() {
+        for raw_prefix in raw.child("prefixItems").elements() {
+            let prefix_schema = match raw_prefix.schema() {
+                Some(s) => s,
+                None => break,
+            };
+            match parse_json_from_schema(
+                prefix_schema,
+                raw_prefix,
+                &inline_ancestors,
+                spec,
+                settings,
+            )? {
+                Some(item) => items.push(item),
+                None => break,
+            }
+        }
+
+        let raw_contains = raw.child("contains");
+        if let Some(contains_schema) = raw_contains.schema() {
+            let min_contains = raw.get("minContains").and_then(|m| m.as_u64()).unwrap_or(1);
+            for _ in 0..min_contains {
+                match parse_json_from_schema(
+                    contains_schema.clone(),
+                    raw_contains,
+                    &inline_ancestors,
+                    spec,
+                    settings,
+                )? {
+                    Some(item) => items.push(item),
+                    None => break,
+                }
+            }
+        }
+    }
+
+    let items_schema = match &schema.items {
+        Some(items_schema) => items_schema,
+        None => return Ok(serde_json::Value::Array(items)),
+    };
+    if ancestors.stops_at(items_schema) {
+        debug!(
+            "Leaving the items out of an array, they're recursive or nested deeper than {} schemas",
+            ancestors.max_depth()
+        );
+        return Ok(serde_json::Value::Array(items));
+    }
+
+    let items_ancestors = ancestors.child(items_schema);
+    let resolved_items = items_schema.resolve(spec)?;
+    let enum_len = raw
+        .items()
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .map(|e| e.len());
+    let count = settings.sample_values.item_count(schema, enum_len);
+    while items.len() < count {
+        let item = match parse_json_from_schema(
+            resolved_items.clone(),
+            raw.items(),
+            &items_ancestors,
+            spec,
+            settings,
+        )? {
+            Some(item) => item,
+            None => break,
+        };
+
+        if !schema.unique_items.unwrap_or(false) || is_unique(&items, &item) {
+            items.push(item);
+            continue;
+        }
+
+        match unique_item(
+            &items,
+            &resolved_items,
+            raw.items(),
+            &items_ancestors,
+            spec,
+            settings,
+        )? {
+            Some(item) => items.push(item),
+            None => {
+                warn!(
+                    "Couldn't generate {count} unique items for an array, generating {}",
+                    items.len()
+                );
+                break;
+            }
+        }
+    }
+
+    Ok(serde_json::Value::Array(items))
+}
+
+/// An item that isn't in the array yet, for arrays with `uniqueItems`. An unused `enum` value
+/// is taken first, otherwise items are generated from random values until one is unique.
+fn unique_item(
+    items: &[serde_json::Value],
+    schema: &Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Option<serde_json::Value>, RefError> {
+    let unused_enum_value = raw
+        .get("enum")
+        .and_then(|e| e.as_array())
+        .into_iter()
+        .flatten()
+        .find(|v| !items.contains(v));
+    if let Some(value) = unused_enum_value {
+        return Ok(Some(value.clone()));
+    }
+
+    for seed in 0..UNIQUE_ITEM_ATTEMPTS {
+        let sample_values = settings.sample_values.reseeded(seed);
+        let settings = SpecBodySettings {
+            sample_values: &sample_values,
+            ..settings.clone()
+        };
+        match parse_json_from_schema(schema.clone(), raw, ancestors, spec, &settings)? {
+            Some(item) if is_unique(items, &item) => return Ok(Some(item)),
+            _ => (),
+        }
+    }
+
+    Ok(None)
+}
+
+/// Items with dynamic values are unique even if they're equal, hurl generates a new value for
+/// every one.
+fn is_unique(items: &[serde_json::Value], item: &serde_json::Value) -> bool {
+    !items.contains(item) || item.to_string().contains(NEW_UUID)
+}
+
 fn parse_json_object(
     schema: &Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
     spec: &Spec,
     settings: &SpecBodySettings,
 ) -> Result<Map<String, serde_json::Value>, RefError> {
+    let only_required = matches!(settings.body_fields, BodyFieldChoice::Required);
+    let required = required_properties(schema, raw, only_required);
     let mut props = Map::new();
-    for prop in &schema.properties {
-        let val = parse_json_from_schema(prop.1.resolve(spec)?, spec, settings)?;
+    for prop in ordered_properties(&schema.properties, raw) {
+        let val = json_property(
+            prop.0,
+            prop.1,
+            required.contains(prop.0),
+            raw.property(prop.0),
+            ancestors,
+            spec,
+            settings,
+        )?;
 
         match val {
             Some(v) => props.insert(prop.0.to_string(), v),
             None => None,
         };
     }
+
+    // The branch that applies depends on the properties generated so far
+    if raw.get("if").is_some() {
+        let branch = match raw
+            .child("if")
+            .accepts(&serde_json::Value::Object(props.clone()))
+        {
+            true => raw.child("then"),
+            false => raw.child("else"),
+        };
+        add_conditional_properties(&mut props, schema, raw, branch, ancestors, spec, settings)?;
+    }
+
+    let pattern_ancestors = ancestors.inline_child();
+    for (pattern, raw_subschema) in raw.child("patternProperties").entries() {
+        if only_required || ancestors.is_at_max_depth() {
+            break;
+        }
+        let subschema = match raw_subschema.schema() {
+            Some(s) => s,
+            None => continue,
+        };
+        let key_schema = Schema {
+            pattern: Some(pattern.to_string()),
+            ..Schema::default()
+        };
+        let key = settings
+            .sample_values
+            .string_from_schema(&key_schema, PATTERN_PROPERTY_KEY);
+
+        if let Some(v) =
+            parse_json_from_schema(subschema, raw_subschema, &pattern_ancestors, spec, settings)?
+        {
+            props.insert(key, v);
+        }
+    }
+
+    // A boolean only allows or forbids additional properties, there's nothing to generate
+    let raw_additional = raw.child("additionalProperties");
+    if let (Some(additional), Some(serde_json::Value::Object(_))) = (
+        &schema.additional_properties,
+        raw.get("additionalProperties"),
+    ) {
+        let additional_ancestors = ancestors.child(additional);
+        let resolved_additional = additional.resolve(spec)?;
+        let missing = (schema.min_properties.unwrap_or(0) as usize).saturating_sub(props.len());
+        // Only as many as minProperties requires when only required properties are chosen
+        let count = match only_required {
+            true => missing,
+            false => missing.max(1),
+        };
+        for i in 1..=count {
+            // Additional properties are optional, recursive ones are left out
+            if ancestors.stops_at(additional)
+                || schema
+                    .max_properties
+                    .is_some_and(|max| props.len() >= max as usize)
+            {
+                break;
+            }
+
+            match parse_json_from_schema(
+                resolved_additional.clone(),
+                raw_additional,
+                &additional_ancestors,
+                spec,
+                settings,
+            )? {
+                Some(v) => props.insert(format!("{ADDITIONAL_PROPERTY_KEY}{i}"), v),
+                None => break,
+            };
+        }
+    }
+
     Ok(props)
 }
 
+/// The schema's `required` properties, and the properties `dependentRequired` requires because
+/// a property they depend on is in the body.
+fn required_properties(schema: &Schema, raw: RawNode, only_required: bool) -> Vec<String> {
+    let mut required = schema.required.clone();
+    let dependencies = match raw.get("dependentRequired").and_then(|d| d.as_object()) {
+        Some(d) => d,
+        None => return required,
+    };
+
+    // Dependent properties can have dependencies of their own
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (name, dependents) in dependencies {
+            let in_body =
+                required.contains(name) || (!only_required && schema.properties.contains_key(name));
+            if !in_body {
+                continue;
+            }
+            for dependent in dependents.as_array().into_iter().flatten() {
+                if let Some(dependent) = dependent.as_str() {
+                    if !required.iter().any(|r| r == dependent) {
+                        required.push(dependent.to_string());
+                        changed = true;
+                    }
+                }
+            }
+        }
+    }
+
+    required
+}
+
+/// Adds the properties the `then` or `else` branch of a conditional schema requires that aren't
+/// in the body yet. A property schema in the branch adds to the schema's own one.
+fn add_conditional_properties(
+    props: &mut Map<String, serde_json::Value>,
+    schema: &Schema,
+    raw: RawNode,
+    branch: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<(), RefError> {
+    let required = branch.get("required").and_then(|r| r.as_array());
+    for name in required.into_iter().flatten().filter_map(|n| n.as_str()) {
+        if props.contains_key(name) {
+            continue;
+        }
+
+        let raw_branch_property = branch.property(name);
+        let value = match (raw_branch_property.schema(), schema.properties.get(name)) {
+            (Some(branch_schema), prop) if !ancestors.is_at_max_depth() => {
+                let property_schema = match prop {
+                    Some(prop) => merge_all_of(
+                        Schema {
+                            all_of: vec![prop.clone(), ObjectOrReference::Object(branch_schema)],
+                            ..Schema::default()
+                        },
+                        spec,
+                    )?,
+                    None => branch_schema,
+                };
+                parse_json_from_schema(
+                    property_schema,
+                    raw_branch_property,
+                    &ancestors.inline_child(),
+                    spec,
+                    settings,
+                )?
+            }
+            (_, Some(prop)) => json_property(
+                name,
+                prop,
+                true,
+                raw.property(name),
+                ancestors,
+                spec,
+                settings,
+            )?,
+            _ => None,
+        };
+
+        if let Some(v) = value {
+            props.insert(name.to_string(), v);
+        }
+    }
+
+    Ok(())
+}
+
+/// Generates the value of a property. Optional properties are left out if only required ones
+/// are chosen. A property the walk stops at is left out, or truncated to an empty array or null
+/// if it's required.
+fn json_property(
+    name: &str,
+    prop: &ObjectOrReference<Schema>,
+    required: bool,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Option<serde_json::Value>, RefError> {
+    // The chosen enum property is set in every body, even if it's optional
+    if settings.enum_property.as_deref() == Some(name) && raw.get("enum").is_some() {
+        if let Some(value) = &settings.enum_value {
+            return Ok(Some(value.clone()));
+        }
+    }
+
+    if !required && matches!(settings.body_fields, BodyFieldChoice::Required) {
+        return Ok(None);
+    }
+
+    let prop_schema = prop.resolve(spec)?;
+    if required && settings.leave_out_required {
+        // A read-only property is never sent, leaving it out doesn't make the body invalid
+        if !prop_schema.read_only.unwrap_or(false) {
+            settings.left_out_required.set(true);
+        }
+        return Ok(None);
+    }
+
+    if settings.explicit_nulls
+        && !prop_schema.read_only.unwrap_or(false)
+        && is_nullable(&prop_schema, raw, spec)?
+    {
+        return Ok(Some(serde_json::Value::Null));
+    }
+
+    let prop_ancestors = ancestors.child(prop);
+    let is_array = matches!(
+        prop_schema.schema_type,
+        Some(SchemaTypeSet::Single(SchemaType::Array))
+    );
+    // An array of recursive items, like the children of a tree node, is recursive itself
+    let stops = ancestors.stops_at(prop)
+        || (is_array
+            && prop_schema
+                .items
+                .as_ref()
+                .is_some_and(|items| prop_ancestors.stops_at(items)));
+
+    if !stops {
+        return parse_json_from_schema(prop_schema, raw, &prop_ancestors, spec, settings);
+    }
+
+    if !required {
+        debug!(
+            "Leaving out the property {name}, it's recursive or nested deeper than {} schemas",
+            ancestors.max_depth()
+        );
+        return Ok(None);
+    }
+
+    warn!(
+        "The required property {name} is recursive or nested deeper than {} schemas, truncating it",
+        ancestors.max_depth()
+    );
+    match is_array {
+        true => Ok(Some(serde_json::Value::Array(vec![]))),
+        false => Ok(Some(serde_json::Value::Null)),
+    }
+}
+
+/// Whether null is a valid value of the schema, because `null` is one of its types, it's
+/// `nullable` in OpenAPI 3.0 or one of its oneOf or anyOf variants is null.
+fn is_nullable(schema: &Schema, raw: RawNode, spec: &Spec) -> Result<bool, RefError> {
+    if schema.is_nullable() == Some(true)
+        || raw.get("nullable") == Some(&serde_json::Value::Bool(true))
+    {
+        return Ok(true);
+    }
+
+    for variant in schema.one_of.iter().chain(&schema.any_of) {
+        if variant.resolve(spec)?.is_nullable() == Some(true) {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+/// The index of the first variant that isn't only null, e.g. of `oneOf: [{type: null}, ...]`.
+fn first_non_null_variant(
+    variants: &[ObjectOrReference<Schema>],
+    spec: &Spec,
+) -> Result<usize, RefError> {
+    for (i, variant) in variants.iter().enumerate() {
+        let is_null = matches!(
+            variant.resolve(spec)?.schema_type,
+            Some(SchemaTypeSet::Single(SchemaType::Null))
+        );
+        if !is_null {
+            return Ok(i);
+        }
+    }
+
+    Ok(0)
+}
+
 enum SimpleJsonValue {
     Scalar(serde_json::Value),
     Array,
     Object,
 }
 
-fn default_json_value_from_schema_type(schema_type: oas3::spec::SchemaType) -> SimpleJsonValue {
+fn default_json_value_from_schema_type(
+    schema_type: oas3::spec::SchemaType,
+    schema: &Schema,
+    settings: &SpecBodySettings,
+) -> SimpleJsonValue {
+    let sample_values = settings.sample_values;
     match schema_type {
-        oas3::spec::SchemaType::Boolean => SimpleJsonValue::Scalar(serde_json::Value::Bool(true)),
-        oas3::spec::SchemaType::Integer => {
-            SimpleJsonValue::Scalar(serde_json::Value::Number(Number::from(3)))
+        oas3::spec::SchemaType::Boolean => {
+            SimpleJsonValue::Scalar(serde_json::Value::Bool(sample_values.boolean(true)))
         }
+        oas3::spec::SchemaType::Integer => SimpleJsonValue::Scalar(serde_json::Value::Number(
+            Number::from(sample_values.integer_from_schema(schema, 3)),
+        )),
         oas3::spec::SchemaType::Number => {
             // Safe to unwrap, only returns None for infinite values or for NaN
-            SimpleJsonValue::Scalar(serde_json::Value::Number(Number::from_f64(3.3).unwrap()))
-        }
-        oas3::spec::SchemaType::String => {
-            SimpleJsonValue::Scalar(serde_json::Value::String("string".to_string()))
+            SimpleJsonValue::Scalar(serde_json::Value::Number(
+                Number::from_f64(sample_values.number_from_schema(schema, 3.3)).unwrap(),
+            ))
         }
+        oas3::spec::SchemaType::String => SimpleJsonValue::Scalar(serde_json::Value::String(
+            sample_values.string_from_schema(schema, "string"),
+        )),
         oas3::spec::SchemaType::Array => SimpleJsonValue::Array,
         oas3::spec::SchemaType::Object => SimpleJsonValue::Object,
         oas3::spec::SchemaType::Null => SimpleJsonValue::Scalar(serde_json::Value::Null),
     }
 }
 
-fn json_obj_from_anyof(
-    anyof: Vec<ObjectOrReference<Schema>>,
+/// Generates the value of one variant of a oneOf or anyOf. If the schema has a discriminator the
+/// discriminating property is set to the value that selects the variant.
+fn json_obj_from_variant(
+    variants: &[ObjectOrReference<Schema>],
+    index: usize,
+    raw: RawNode,
+    keyword: &str,
+    ancestors: &SchemaAncestors,
     spec: &Spec,
     settings: &SpecBodySettings,
 ) -> Result<Option<serde_json::Value>, RefError> {
-    for schema in &anyof {
-        return parse_json_from_schema(schema.resolve(spec)?, spec, &settings);
-    }
-
-    Ok(Some(serde_json::Value::Object(Map::new())))
-}
+    let variant = match variants.get(index) {
+        Some(v) => v,
+        None => return Ok(Some(serde_json::Value::Object(Map::new()))),
+    };
 
-fn json_obj_from_allof(
-    schema: Schema,
-    spec: &Spec,
-    settings: &SpecBodySettings,
-) -> Result<serde_json::Value, RefError> {
-    let mut props = Map::new();
-    for subschema in schema.all_of {
-        for prop in subschema.resolve(spec)?.properties {
-            let value = parse_json_from_schema(prop.1.resolve(spec)?, spec, &settings)?;
-            match value {
-                Some(v) => {
-                    props.insert(prop.0, v);
-                }
-                None => (),
-            };
-        }
+    if ancestors.stops_at(variant) {
+        debug!(
+            "Leaving out a {keyword} variant, it's recursive or nested deeper than {} schemas",
+            ancestors.max_depth()
+        );
+        return Ok(None);
     }
 
-    for prop in schema.properties {
-        let value = parse_json_from_schema(prop.1.resolve(spec)?, spec, &settings)?;
-        match value {
+    let mut value = parse_json_from_schema(
+        variant.resolve(spec)?,
+        raw.child(keyword).child(&index.to_string()),
+        &ancestors.child(variant),
+        spec,
+        settings,
+    )?;
+
+    let discriminator = raw.child("discriminator");
+    let property_name = discriminator.get("propertyName").and_then(|p| p.as_str());
+    if let (Some(serde_json::Value::Object(props)), Some(property_name)) =
+        (value.as_mut(), property_name)
+    {
+        match discriminator_value(variant, discriminator) {
             Some(v) => {
-                props.insert(prop.0, v);
+                props.insert(property_name.to_string(), serde_json::Value::String(v));
             }
-            None => (),
-        };
+            None => debug!(
+                "Couldn't find the value of the discriminator {property_name} for an inline schema"
+            ),
+        }
     }
 
-    Ok(serde_json::Value::Object(props))
+    Ok(value)
+}
+
+/// The value of the discriminator that selects the variant. A value mapped to the variant's
+/// reference takes precedence over the name of the referenced schema.
+/// See https://spec.openapis.org/oas/v3.1.0#discriminator-object
+fn discriminator_value(
+    variant: &ObjectOrReference<Schema>,
+    discriminator: RawNode,
+) -> Option<String> {
+    let ref_path = match variant {
+        ObjectOrReference::Ref { ref_path } => ref_path,
+        ObjectOrReference::Object(_) => return None,
+    };
+    let schema_name = ref_path.rsplit('/').next()?;
+
+    let mapped = discriminator
+        .get("mapping")
+        .and_then(|m| m.as_object())
+        .and_then(|mapping| {
+            mapping.iter().find(|(_, target)| {
+                target
+                    .as_str()
+                    .is_some_and(|t| t == ref_path || t == schema_name)
+            })
+        })
+        .map(|(value, _)| value.to_string());
+
+    Some(mapped.unwrap_or(schema_name.to_string()))
 }
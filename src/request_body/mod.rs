@@ -1,2 +1,5 @@
+mod form_request_body;
 mod json_request_body;
+mod multipart_request_body;
 pub mod request_body;
+mod xml_request_body;
@@ -0,0 +1,138 @@
+use std::collections::BTreeMap;
+
+use hurl_core::ast::{FileParam, FileValue, MultipartParam};
+use log::debug;
+use oas3::{
+    spec::{Encoding, RefError, SchemaType, SchemaTypeSet},
+    Schema, Spec,
+};
+
+use super::{
+    json_request_body::parse_json_from_schema,
+    request_body::{param_value_from_json, SpecBodySettings},
+};
+use crate::{
+    custom_hurl_ast::{empty_space, key_template, key_value, newline, unquoted_template},
+    raw_spec::{ordered_properties, RawNode},
+    schema_ancestors::SchemaAncestors,
+};
+
+const BINARY_FORMAT: &str = "binary";
+
+pub fn parse_multipart_from_schema(
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    encoding: &BTreeMap<String, Encoding>,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Vec<MultipartParam>, RefError> {
+    let mut params = vec![];
+
+    for prop in ordered_properties(&schema.properties, raw) {
+        let prop_schema = prop.1.resolve(spec)?;
+        if prop_schema.read_only.unwrap_or(false) {
+            continue;
+        }
+
+        if ancestors.stops_at(prop.1) {
+            debug!(
+                "Leaving out the part {}, it's recursive or nested deeper than {} schemas",
+                prop.0,
+                ancestors.max_depth()
+            );
+            continue;
+        }
+
+        let part_content_type = encoding
+            .get(prop.0)
+            .and_then(|e| e.content_type.clone())
+            // The encoding can list several content types, any of them will do
+            .and_then(|ct| ct.split(',').next().map(|ct| ct.trim().to_string()));
+
+        if is_binary(&prop_schema, spec)? {
+            params.push(MultipartParam::FileParam(file_param(
+                prop.0,
+                part_content_type,
+            )));
+            continue;
+        }
+
+        if let Some(ct) = &part_content_type {
+            if !ct.contains("json") && !ct.starts_with("text/") {
+                debug!("hurl can only set the content type of file parts, the part {} will be sent without the content type {ct}", prop.0);
+            }
+        }
+
+        if let Some(v) = parse_json_from_schema(
+            prop_schema,
+            raw.property(prop.0),
+            &ancestors.child(prop.1),
+            spec,
+            settings,
+        )? {
+            params.push(MultipartParam::Param(key_value(
+                prop.0.to_string(),
+                param_value_from_json(&v),
+            )));
+        }
+    }
+
+    Ok(params)
+}
+
+/// Binary strings, and arrays of binary strings, are uploaded as files.
+fn is_binary(schema: &Schema, spec: &Spec) -> Result<bool, RefError> {
+    if schema.format.as_deref() == Some(BINARY_FORMAT) {
+        return Ok(true);
+    }
+
+    let is_array = matches!(
+        schema.schema_type,
+        Some(SchemaTypeSet::Single(SchemaType::Array))
+    );
+
+    match &schema.items {
+        Some(items) if is_array => {
+            Ok(items.resolve(spec)?.format.as_deref() == Some(BINARY_FORMAT))
+        }
+        _ => Ok(false),
+    }
+}
+
+fn file_param(name: &str, content_type: Option<String>) -> FileParam {
+    FileParam {
+        line_terminators: vec![],
+        space0: empty_space(),
+        key: key_template(name.to_string()),
+        space1: empty_space(),
+        space2: crate::hurl_files::single_space(),
+        value: FileValue {
+            space0: empty_space(),
+            filename: unquoted_template(format!("{{{{{}}}}}", file_variable_name(name))),
+            space1: empty_space(),
+            space2: match content_type {
+                Some(_) => crate::hurl_files::single_space(),
+                None => empty_space(),
+            },
+            content_type,
+        },
+        line_terminator0: newline(),
+    }
+}
+
+/// Hurl variables can only contain alphanumeric characters, `_` and `-`.
+fn file_variable_name(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+
+    format!("{sanitized}_file")
+}
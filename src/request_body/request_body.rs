@@ -1,43 +1,136 @@
-use super::json_request_body::parse_json_from_schema;
-use crate::content_type::ContentType;
+use super::form_request_body::parse_form_params_from_schema;
+use super::json_request_body::parse_json_bodies_from_schema;
+use super::multipart_request_body::parse_multipart_from_schema;
+use super::xml_request_body::parse_xml_from_schema;
+use crate::content_type::{preferred_content, ContentType};
+use crate::raw_spec::RawNode;
+use crate::sample_values::values::SampleValues;
+use crate::schema_ancestors::{SchemaAncestors, DEFAULT_MAX_DEPTH};
 use crate::Settings;
 use crate::{
-    cli::Formatting,
-    custom_hurl_ast::{empty_source_info, empty_space, newline},
+    cli::{
+        BodyFieldChoice, Formatting, NullValueChoice, RequestMediaTypeChoice, UnionVariantChoice,
+    },
+    custom_hurl_ast::{empty_source_info, empty_space, newline, unquoted_template},
 };
 use anyhow::Context;
-use hurl_core::ast::{Body, MultilineString, TemplateElement};
+use hurl_core::ast::{Body, File, MultilineString, Section, SectionValue, TemplateElement};
 use log::{debug, trace, warn};
 use oas3::{
-    spec::{RefError, RequestBody},
+    spec::{MediaType, ObjectOrReference, RefError, RequestBody},
     Schema, Spec,
 };
+use std::{cell::Cell, rc::Rc};
 
-pub struct SpecBodySettings {
+const XML_ROOT_NAME: &str = "root";
+const UPLOAD_FILE_VARIABLE: &str = "upload_file";
+
+#[derive(Clone)]
+pub struct SpecBodySettings<'a> {
     pub formatting: Formatting,
+    pub body_fields: BodyFieldChoice,
+    pub enum_property: Option<String>,
+    /// The value of the enum property in the body being generated.
+    pub enum_value: Option<serde_json::Value>,
+    pub null_values: NullValueChoice,
+    /// Whether nullable properties are null in the body being generated.
+    pub explicit_nulls: bool,
+    /// Whether required properties are left out of the body being generated, making it invalid.
+    pub leave_out_required: bool,
+    /// Set once a required property is left out, only bodies that are invalid are generated.
+    pub left_out_required: Rc<Cell<bool>>,
     pub content_type: ContentType,
+    pub media_types: Vec<String>,
+    pub request_media_types: RequestMediaTypeChoice,
+    pub union_variants: UnionVariantChoice,
+    pub max_depth: usize,
+    pub sample_values: &'a SampleValues,
 }
 
-impl SpecBodySettings {
-    pub fn from_settings(settings: &Settings) -> Self {
+impl<'a> SpecBodySettings<'a> {
+    pub fn from_settings(settings: &'a Settings) -> Self {
         Self {
             formatting: settings.formatting.clone(),
+            body_fields: settings.body_fields.clone(),
+            enum_property: settings.enum_property.clone(),
+            enum_value: None,
+            null_values: settings.null_values.clone(),
+            explicit_nulls: false,
+            leave_out_required: false,
+            left_out_required: Rc::new(Cell::new(false)),
             content_type: settings.content_type.clone(),
+            media_types: settings.media_types.clone(),
+            request_media_types: settings.request_media_types.clone(),
+            union_variants: settings.union_variants.clone(),
+            max_depth: settings.max_depth.unwrap_or(DEFAULT_MAX_DEPTH),
+            sample_values: &settings.sample_values,
         }
     }
 }
 
+/// The request body of a hurl entry and the media type it was generated for.
+pub struct SpecBody {
+    pub media_type: String,
+    pub value: SpecBodyValue,
+}
+
+pub enum SpecBodyValue {
+    Body(Body),
+    /// A section of the request, e.g. `[MultipartFormData]`.
+    Section(Section),
+    /// A file sent as the body, hurl won't set a content type for it.
+    File(Body),
+}
+
 pub fn from_spec_body(
     spec_body: RequestBody,
     spec: &Spec,
+    raw_body: RawNode,
     operation_id: String,
     settings: SpecBodySettings,
-) -> Result<Option<Body>, RefError> {
-    let content = match spec_body
-        .content
-        .iter()
-        .find(|c| settings.content_type.matches_string(c.0))
-    {
+) -> Result<Vec<SpecBody>, RefError> {
+    match settings.request_media_types {
+        RequestMediaTypeChoice::Preferred => {
+            preferred_spec_body(&spec_body, spec, raw_body, &operation_id, &settings)
+        }
+        RequestMediaTypeChoice::All => {
+            let mut bodies = vec![];
+            for content in spec_body
+                .content
+                .iter()
+                .filter(|c| ContentType::from_string(c.0).is_ok())
+            {
+                bodies.extend(from_content(
+                    content,
+                    spec,
+                    raw_body,
+                    &operation_id,
+                    &settings,
+                )?);
+            }
+
+            if bodies.is_empty() {
+                warn!("operation {operation_id} does not have any of the supported content types ({}). Defaulting to an empty request body", ContentType::supported_types().join(", "));
+            }
+
+            Ok(bodies)
+        }
+    }
+}
+
+fn preferred_spec_body(
+    spec_body: &RequestBody,
+    spec: &Spec,
+    raw_body: RawNode,
+    operation_id: &str,
+    settings: &SpecBodySettings,
+) -> Result<Vec<SpecBody>, RefError> {
+    let content = match preferred_content(&spec_body.content, &settings.media_types).or_else(|| {
+        spec_body
+            .content
+            .iter()
+            .find(|c| settings.content_type.matches_string(c.0))
+    }) {
         Some(c) => c,
         None => match spec_body
             .content
@@ -50,30 +143,99 @@ pub fn from_spec_body(
             }
             None => {
                 warn!("operation {operation_id} does not have any of the supported content types ({}). Defaulting to an empty request body", ContentType::supported_types().join(", "));
-                return Ok(None);
+                return Ok(vec![]);
             }
         },
     };
 
-    let schema = match &content.1.schema {
-        Some(s) => s.resolve(spec)?,
-        None => return Ok(None),
-    };
+    from_content(content, spec, raw_body, operation_id, settings)
+}
 
+fn from_content(
+    content: (&String, &MediaType),
+    spec: &Spec,
+    raw_body: RawNode,
+    operation_id: &str,
+    settings: &SpecBodySettings,
+) -> Result<Vec<SpecBody>, RefError> {
     let content_type = match ContentType::from_string(content.0) {
         Ok(ct) => ct,
         Err(_) => {
             warn!("operation {operation_id} does not have any of the supported content types ({}). Defaulting to an empty request body", ContentType::supported_types().join(", "));
-            return Ok(None);
+            return Ok(vec![]);
         }
     };
 
-    match content_type {
+    if settings.leave_out_required {
+        settings.left_out_required.set(false);
+        let bodies = from_content_bodies(
+            content,
+            content_type,
+            spec,
+            raw_body,
+            operation_id,
+            settings,
+        )?;
+        if !settings.left_out_required.get() {
+            debug!("the {} request body of operation {operation_id} has no required properties to leave out", content.0);
+            return Ok(vec![]);
+        }
+        return Ok(bodies);
+    }
+
+    from_content_bodies(
+        content,
+        content_type,
+        spec,
+        raw_body,
+        operation_id,
+        settings,
+    )
+}
+
+fn from_content_bodies(
+    content: (&String, &MediaType),
+    content_type: ContentType,
+    spec: &Spec,
+    raw_body: RawNode,
+    operation_id: &str,
+    settings: &SpecBodySettings,
+) -> Result<Vec<SpecBody>, RefError> {
+    // The schema of a binary body only describes the bytes, the file is what matters
+    if let ContentType::Binary = content_type {
+        trace!("using a file as the request body");
+        return Ok(vec![SpecBody {
+            media_type: file_media_type(content.0),
+            value: SpecBodyValue::File(Body {
+                line_terminators: vec![],
+                space0: empty_space(),
+                value: hurl_core::ast::Bytes::File(File {
+                    space0: empty_space(),
+                    filename: unquoted_template(format!("{{{{{UPLOAD_FILE_VARIABLE}}}}}")),
+                    space1: empty_space(),
+                }),
+                line_terminator0: newline(),
+            }),
+        }]);
+    }
+
+    let schema_ref = match &content.1.schema {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+    let schema = schema_ref.resolve(spec)?;
+    let root = SchemaAncestors::root(settings.max_depth);
+    let ancestors = root.child(schema_ref);
+    let raw_schema = raw_body.child("content").child(content.0).child("schema");
+
+    let value = match content_type {
         ContentType::Json => {
             trace!("parsing JSON request body");
-            match parse_json_from_schema(schema, spec, &settings)? {
-                Some(v) => match to_json_string(&v, settings) {
-                    Ok(inner_json) => Ok(Some(Body {
+            let mut bodies = vec![];
+            for v in parse_json_bodies_from_schema(schema, raw_schema, &ancestors, spec, settings)?
+            {
+                match to_json_string(&v, settings) {
+                    Ok(inner_json) => bodies.push(SpecBodyValue::Body(Body {
                         line_terminators: vec![],
                         space0: empty_space(),
                         value: hurl_core::ast::Bytes::MultilineString(MultilineString::Json(text(
@@ -84,22 +246,122 @@ pub fn from_spec_body(
                     Err(e) => {
                         // There's no real reason this should happen.
                         debug!("Could not transform the specification for {operation_id} to JSON {e}. Defaulting to empty request body");
-
-                        Ok(None)
                     }
-                },
-                None => Ok(None),
+                }
             }
+
+            return Ok(bodies
+                .into_iter()
+                .map(|value| SpecBody {
+                    media_type: content.0.to_string(),
+                    value,
+                })
+                .collect());
         }
-        ContentType::Text => match parse_plain_text(schema)? {
-            Some(v) => Ok(Some(Body {
+        ContentType::Text => parse_plain_text(schema)?.map(|v| {
+            SpecBodyValue::Body(Body {
                 line_terminators: vec![],
                 space0: empty_space(),
                 value: hurl_core::ast::Bytes::MultilineString(v),
                 line_terminator0: newline(),
-            })),
-            None => Ok(None),
-        },
+            })
+        }),
+        ContentType::MultipartFormData => {
+            trace!("parsing multipart form data request body");
+            let params = parse_multipart_from_schema(
+                schema,
+                raw_schema,
+                &ancestors,
+                &content.1.encoding,
+                spec,
+                settings,
+            )?;
+
+            if params.is_empty() {
+                return Ok(vec![]);
+            }
+
+            Some(SpecBodyValue::Section(Section {
+                line_terminators: vec![],
+                space0: empty_space(),
+                line_terminator0: newline(),
+                value: SectionValue::MultipartFormData(params),
+                source_info: empty_source_info(),
+            }))
+        }
+        ContentType::Xml => {
+            trace!("parsing XML request body");
+            // Like the spec's examples, the root element is named after the referenced schema
+            let root_name = match &content.1.schema {
+                Some(ObjectOrReference::Ref { ref_path }) => {
+                    ref_path.rsplit('/').next().unwrap_or(XML_ROOT_NAME)
+                }
+                _ => XML_ROOT_NAME,
+            };
+
+            parse_xml_from_schema(root_name, schema, raw_schema, &ancestors, spec, settings)?.map(
+                |xml| {
+                    SpecBodyValue::Body(Body {
+                        line_terminators: vec![],
+                        space0: empty_space(),
+                        value: hurl_core::ast::Bytes::MultilineString(MultilineString::Xml(text(
+                            xml,
+                        ))),
+                        line_terminator0: newline(),
+                    })
+                },
+            )
+        }
+        ContentType::FormUrlEncoded => {
+            trace!("parsing url encoded form request body");
+            let params = parse_form_params_from_schema(
+                schema,
+                raw_schema,
+                &ancestors,
+                &content.1.encoding,
+                spec,
+                settings,
+            )?;
+
+            if params.is_empty() {
+                return Ok(vec![]);
+            }
+
+            Some(SpecBodyValue::Section(Section {
+                line_terminators: vec![],
+                space0: empty_space(),
+                line_terminator0: newline(),
+                value: SectionValue::FormParams(params),
+                source_info: empty_source_info(),
+            }))
+        }
+        // Handled above
+        ContentType::Binary => None,
+    };
+
+    Ok(value
+        .map(|value| SpecBody {
+            media_type: content.0.to_string(),
+            value,
+        })
+        .into_iter()
+        .collect())
+}
+
+/// Binary media types can be ranges like `image/*`, these can't be used as a content type.
+fn file_media_type(media_type: &str) -> String {
+    if media_type.contains('*') {
+        ContentType::Binary.to_str().to_string()
+    } else {
+        media_type.to_string()
+    }
+}
+
+/// Form fields are sent as strings, anything that isn't already a string is sent as JSON.
+pub fn param_value_from_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.to_string(),
+        v => v.to_string(),
     }
 }
 
@@ -155,7 +417,7 @@ fn text(element: String) -> hurl_core::ast::Text {
 
 fn to_json_string(
     json_value: &serde_json::Value,
-    settings: SpecBodySettings,
+    settings: &SpecBodySettings,
 ) -> Result<String, anyhow::Error> {
     match settings.formatting {
         Formatting::NoFormatting => {
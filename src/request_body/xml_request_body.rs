@@ -0,0 +1,232 @@
+use oas3::{spec::RefError, Schema, Spec};
+use serde_json::Value;
+
+use super::{json_request_body::parse_json_from_schema, request_body::SpecBodySettings};
+use crate::{
+    cli::Formatting, raw_spec::RawNode, schema_ancestors::SchemaAncestors,
+    schema_composition::merge_all_of,
+};
+
+const INDENT: &str = "  ";
+
+/// The OpenAPI XML object, see https://spec.openapis.org/oas/v3.1.0#xml-object
+#[derive(Default)]
+struct XmlObject {
+    name: Option<String>,
+    namespace: Option<String>,
+    prefix: Option<String>,
+    attribute: bool,
+    wrapped: bool,
+}
+
+impl XmlObject {
+    fn from_raw(raw: RawNode) -> Self {
+        let xml = match raw.get("xml") {
+            Some(xml) => xml,
+            None => return Self::default(),
+        };
+
+        let string = |key: &str| xml.get(key).and_then(|v| v.as_str()).map(|v| v.to_string());
+        let boolean = |key: &str| xml.get(key).and_then(|v| v.as_bool()).unwrap_or(false);
+
+        Self {
+            name: string("name"),
+            namespace: string("namespace"),
+            prefix: string("prefix"),
+            attribute: boolean("attribute"),
+            wrapped: boolean("wrapped"),
+        }
+    }
+
+    fn qualified_name(&self, default_name: &str) -> String {
+        let name = self.name.as_deref().unwrap_or(default_name);
+        match &self.prefix {
+            Some(prefix) => format!("{prefix}:{name}"),
+            None => name.to_string(),
+        }
+    }
+
+    fn namespace_attribute(&self) -> Option<(String, String)> {
+        let namespace = self.namespace.clone()?;
+        match &self.prefix {
+            Some(prefix) => Some((format!("xmlns:{prefix}"), namespace)),
+            None => Some(("xmlns".to_string(), namespace)),
+        }
+    }
+}
+
+/// Builds an XML document from the schema. Values are generated in the same way as for JSON
+/// bodies, the schema's `xml` objects decide how those values are laid out as elements and
+/// attributes.
+pub fn parse_xml_from_schema(
+    root_name: &str,
+    schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
+    spec: &Spec,
+    settings: &SpecBodySettings,
+) -> Result<Option<String>, RefError> {
+    let value = match parse_json_from_schema(schema.clone(), raw, ancestors, spec, settings)? {
+        Some(v) => v,
+        None => return Ok(None),
+    };
+
+    let mut writer = XmlWriter {
+        xml: String::new(),
+        spec,
+        pretty: matches!(settings.formatting, Formatting::RequestBodies),
+    };
+    writer.write_value(root_name, &value, &schema, raw, 0)?;
+
+    Ok(Some(writer.xml.trim_end().to_string()))
+}
+
+struct XmlWriter<'a> {
+    xml: String,
+    spec: &'a Spec,
+    pretty: bool,
+}
+
+impl<'a> XmlWriter<'a> {
+    fn write_value(
+        &mut self,
+        name: &str,
+        value: &Value,
+        schema: &Schema,
+        raw: RawNode,
+        depth: usize,
+    ) -> Result<(), RefError> {
+        let xml = XmlObject::from_raw(raw);
+        let element_name = xml.qualified_name(name);
+        let mut attributes: Vec<(String, String)> = xml.namespace_attribute().into_iter().collect();
+
+        match value {
+            Value::Array(items) => {
+                let items_schema = match &schema.items {
+                    Some(s) => s.resolve(self.spec)?,
+                    None => Schema::default(),
+                };
+
+                // Items are named after the property unless they have a name of their own
+                let item_depth = if xml.wrapped { depth + 1 } else { depth };
+                if xml.wrapped {
+                    self.open_tag(&element_name, &attributes, depth, false);
+                }
+                for item in items {
+                    self.write_value(name, item, &items_schema, raw.items(), item_depth)?;
+                }
+                if xml.wrapped {
+                    self.close_tag(&element_name, depth, true);
+                }
+            }
+            Value::Object(props) => {
+                let schema = merge_all_of(schema.clone(), self.spec)?;
+                let mut children = vec![];
+                for prop in props {
+                    let (prop_schema, prop_raw) = self.property_schema(&schema, prop.0, raw)?;
+
+                    if XmlObject::from_raw(prop_raw).attribute {
+                        attributes.push((
+                            XmlObject::from_raw(prop_raw).qualified_name(prop.0),
+                            text(prop.1),
+                        ));
+                    } else {
+                        children.push((prop.0, prop.1, prop_schema, prop_raw));
+                    }
+                }
+
+                if children.is_empty() {
+                    self.open_tag(&element_name, &attributes, depth, true);
+                    return Ok(());
+                }
+
+                self.open_tag(&element_name, &attributes, depth, false);
+                for child in children {
+                    self.write_value(child.0, child.1, &child.2, child.3, depth + 1)?;
+                }
+                self.close_tag(&element_name, depth, true);
+            }
+            Value::Null => self.open_tag(&element_name, &attributes, depth, true),
+            v => {
+                self.open_tag(&element_name, &attributes, depth, false);
+                self.xml.push_str(&escape(&text(v)));
+                self.close_tag(&element_name, depth, false);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn property_schema<'b>(
+        &self,
+        schema: &Schema,
+        name: &str,
+        raw: RawNode<'b>,
+    ) -> Result<(Schema, RawNode<'b>), RefError> {
+        let property_schema = match schema.properties.get(name) {
+            Some(p) => p.resolve(self.spec)?,
+            None => Schema::default(),
+        };
+
+        Ok((property_schema, raw.property(name)))
+    }
+
+    fn open_tag(
+        &mut self,
+        name: &str,
+        attributes: &[(String, String)],
+        depth: usize,
+        self_closing: bool,
+    ) {
+        self.indent(depth);
+        self.xml.push('<');
+        self.xml.push_str(name);
+        for attribute in attributes {
+            self.xml
+                .push_str(&format!(" {}=\"{}\"", attribute.0, escape(&attribute.1)));
+        }
+        self.xml.push_str(if self_closing { "/>" } else { ">" });
+        if self_closing {
+            self.newline();
+        }
+    }
+
+    fn close_tag(&mut self, name: &str, depth: usize, on_own_line: bool) {
+        if on_own_line {
+            self.indent(depth);
+        }
+        self.xml.push_str(&format!("</{name}>"));
+        self.newline();
+    }
+
+    fn indent(&mut self, depth: usize) {
+        if self.pretty {
+            // Opening tags of elements with children end with a newline
+            if !self.xml.is_empty() && !self.xml.ends_with('\n') {
+                self.xml.push('\n');
+            }
+            self.xml.push_str(&INDENT.repeat(depth));
+        }
+    }
+
+    fn newline(&mut self) {
+        if self.pretty {
+            self.xml.push('\n');
+        }
+    }
+}
+
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        Value::Null => "".to_string(),
+        v => v.to_string(),
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -32,7 +32,7 @@ pub fn parse_string_asserts(schema: Schema, query_value: &QueryValue) -> Vec<Ass
     add_string_schema_asserts_to_asserts(schema, query_value, asserts)
 }
 
-fn add_string_schema_asserts_to_asserts(
+pub fn add_string_schema_asserts_to_asserts(
     schema: Schema,
     query_value: &QueryValue,
     mut asserts: Vec<Assert>,
@@ -0,0 +1,310 @@
+use std::collections::BTreeMap;
+
+use hurl_core::ast::{Assert, Filter, FilterValue, PredicateFuncValue, PredicateValue, QueryValue};
+use log::debug;
+use oas3::{
+    spec::{Header, ObjectOrReference, RefError, SchemaType, SchemaTypeSet},
+    Schema, Spec,
+};
+
+use super::{
+    common_asserts::{
+        add_string_schema_asserts_to_asserts, assert_query_matches_predicate,
+        assert_query_matches_predicate_with_filters, regex_from_pattern,
+    },
+    json_asserts::{predicate_integer_number, simple_template},
+    response_validation::HandleUnionsBy,
+};
+use crate::{
+    custom_hurl_ast::empty_source_info,
+    hurl_files::single_space,
+    raw_spec::{ordered_entries, RawNode},
+};
+
+const CONTENT_TYPE_HEADER: &str = "Content-Type";
+
+/// Asserts the response has the documented headers, and that their values match the headers'
+/// schemas. Optional headers are only asserted when treating optionals as required, as the
+/// response doesn't have to include them. A documented `Content-Type` header is ignored, as the
+/// specification requires, see `content_type_assert` instead.
+pub fn parse_response_header_asserts(
+    headers: &BTreeMap<String, ObjectOrReference<Header>>,
+    raw: RawNode,
+    spec: &Spec,
+    handle_unions_by: &HandleUnionsBy,
+) -> Result<Vec<Assert>, RefError> {
+    let mut asserts = vec![];
+
+    let names: Vec<&str> = raw.entries().into_iter().map(|e| e.0).collect();
+
+    for (name, header) in ordered_entries(headers, &names) {
+        if name.eq_ignore_ascii_case(CONTENT_TYPE_HEADER) {
+            debug!("Ignoring the documented {name} header, the media type is asserted instead");
+            continue;
+        }
+
+        let header = header.resolve(spec)?;
+        let required = header.required.unwrap_or(false)
+            || matches!(
+                handle_unions_by,
+                HandleUnionsBy::TreatingOptionalsAsRequired
+            );
+        if !required {
+            continue;
+        }
+
+        let query_value = header_query(name);
+        asserts.push(assert_query_matches_predicate(
+            &query_value,
+            PredicateFuncValue::Exist,
+        ));
+
+        if let Some(schema) = header.schema {
+            asserts.extend(header_value_asserts(schema, &query_value));
+        }
+    }
+
+    Ok(asserts)
+}
+
+/// Asserts the response's `Content-Type` is the media type its body is validated against, e.g.
+/// `application/json`. Only the part before a wildcard is asserted for media type ranges like
+/// `text/*`, nothing is asserted for `*/*`.
+pub fn content_type_assert(media_type: &str) -> Option<Assert> {
+    let expected = media_type.split('*').next().unwrap_or_default();
+    if expected.is_empty() {
+        return None;
+    }
+
+    Some(assert_query_matches_predicate(
+        &header_query(CONTENT_TYPE_HEADER),
+        PredicateFuncValue::StartWith {
+            space0: single_space(),
+            value: PredicateValue::String(simple_template(expected.to_string())),
+        },
+    ))
+}
+
+/// Header values are text, so their types are asserted by the format of the text.
+fn header_value_asserts(schema: Schema, query_value: &QueryValue) -> Vec<Assert> {
+    if !schema.enum_values.is_empty() {
+        let values = schema
+            .enum_values
+            .iter()
+            .map(|v| regex::escape(v))
+            .collect::<Vec<_>>()
+            .join("|");
+        return vec![matches_pattern(query_value, format!("^({values})$"))];
+    }
+
+    match &schema.schema_type {
+        Some(SchemaTypeSet::Single(SchemaType::Integer)) => {
+            let mut asserts = vec![matches_pattern(query_value, r"^-?\d+$".to_string())];
+            asserts.extend(integer_bound_asserts(schema, query_value));
+            asserts
+        }
+        Some(SchemaTypeSet::Single(SchemaType::Number)) => {
+            vec![matches_pattern(
+                query_value,
+                r"^-?\d+(\.\d+)?([eE][+-]?\d+)?$".to_string(),
+            )]
+        }
+        Some(SchemaTypeSet::Single(SchemaType::Boolean)) => {
+            vec![matches_pattern(query_value, "^(true|false)$".to_string())]
+        }
+        Some(SchemaTypeSet::Single(SchemaType::String)) => {
+            add_string_schema_asserts_to_asserts(schema, query_value, vec![])
+        }
+        _ => vec![],
+    }
+}
+
+/// Bounds are asserted on the value converted to an integer, e.g. `header "X" toInt >= 0`.
+fn integer_bound_asserts(schema: Schema, query_value: &QueryValue) -> Vec<Assert> {
+    let bounds = [
+        schema
+            .minimum
+            .map(|n| PredicateFuncValue::GreaterThanOrEqual {
+                space0: single_space(),
+                value: predicate_integer_number(n),
+                operator: true,
+            }),
+        schema
+            .exclusive_minimum
+            .map(|n| PredicateFuncValue::GreaterThan {
+                space0: single_space(),
+                value: predicate_integer_number(n),
+                operator: true,
+            }),
+        schema.maximum.map(|n| PredicateFuncValue::LessThanOrEqual {
+            space0: single_space(),
+            value: predicate_integer_number(n),
+            operator: true,
+        }),
+        schema
+            .exclusive_maximum
+            .map(|n| PredicateFuncValue::LessThan {
+                space0: single_space(),
+                value: predicate_integer_number(n),
+                operator: true,
+            }),
+    ];
+
+    bounds
+        .into_iter()
+        .flatten()
+        .map(|predicate| {
+            assert_query_matches_predicate_with_filters(
+                query_value,
+                predicate,
+                vec![Filter {
+                    source_info: empty_source_info(),
+                    value: FilterValue::ToInt,
+                }],
+                None,
+            )
+        })
+        .collect()
+}
+
+fn matches_pattern(query_value: &QueryValue, pattern: String) -> Assert {
+    assert_query_matches_predicate(
+        query_value,
+        PredicateFuncValue::Match {
+            space0: single_space(),
+            value: PredicateValue::Regex(regex_from_pattern(pattern)),
+        },
+    )
+}
+
+fn header_query(name: &str) -> QueryValue {
+    QueryValue::Header {
+        space0: single_space(),
+        name: simple_template(name.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Filter, FilterValue, PredicateFuncValue, PredicateValue};
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        content_type_assert, header_query, matches_pattern, parse_response_header_asserts,
+    };
+    use crate::{
+        custom_hurl_ast::empty_source_info,
+        hurl_files::single_space,
+        raw_spec::RawSpec,
+        response::{
+            common_asserts::{
+                assert_query_matches_predicate, assert_query_matches_predicate_with_filters,
+            },
+            json_asserts::simple_template,
+            response_validation::HandleUnionsBy,
+        },
+    };
+
+    const SPEC: &str = r##"{
+        "openapi": "3.1.0",
+        "info": {"title": "Test", "version": "1.0.0"},
+        "paths": {},
+        "components": {
+            "headers": {
+                "RateLimitRemaining": {
+                    "required": true,
+                    "schema": {"type": "integer", "minimum": 0}
+                }
+            },
+            "responses": {
+                "Created": {
+                    "description": "Created",
+                    "headers": {
+                        "Location": {"required": true, "schema": {"type": "string", "pattern": "^/pets/"}},
+                        "X-RateLimit-Remaining": {"$ref": "#/components/headers/RateLimitRemaining"},
+                        "ETag": {"schema": {"type": "string"}},
+                        "Content-Type": {"required": true, "schema": {"type": "string"}}
+                    }
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn parse_response_header_asserts_in_declaration_order() {
+        let spec = oas3::from_str(SPEC).unwrap();
+        let raw_spec = RawSpec::from_str(SPEC).unwrap();
+        let response = spec.components.as_ref().unwrap().responses["Created"]
+            .resolve(&spec)
+            .unwrap();
+        let raw = raw_spec
+            .root()
+            .child("components")
+            .child("responses")
+            .child("Created")
+            .child("headers");
+
+        let asserts = parse_response_header_asserts(
+            &response.headers,
+            raw,
+            &spec,
+            &HandleUnionsBy::IgnoringThem,
+        )
+        .unwrap();
+
+        let location = header_query("Location");
+        let remaining = header_query("X-RateLimit-Remaining");
+        assert_eq!(
+            vec![
+                assert_query_matches_predicate(&location, PredicateFuncValue::Exist),
+                matches_pattern(&location, "^/pets/".to_string()),
+                assert_query_matches_predicate(&remaining, PredicateFuncValue::Exist),
+                matches_pattern(&remaining, r"^-?\d+$".to_string()),
+                assert_query_matches_predicate_with_filters(
+                    &remaining,
+                    PredicateFuncValue::GreaterThanOrEqual {
+                        space0: single_space(),
+                        value: PredicateValue::Number(hurl_core::ast::Number::Integer(0)),
+                        operator: true,
+                    },
+                    vec![Filter {
+                        source_info: empty_source_info(),
+                        value: FilterValue::ToInt,
+                    }],
+                    None,
+                ),
+            ],
+            asserts
+        );
+
+        let asserts = parse_response_header_asserts(
+            &response.headers,
+            raw,
+            &spec,
+            &HandleUnionsBy::TreatingOptionalsAsRequired,
+        )
+        .unwrap();
+        assert_eq!(
+            Some(&assert_query_matches_predicate(
+                &header_query("ETag"),
+                PredicateFuncValue::Exist
+            )),
+            asserts.last()
+        );
+    }
+
+    #[test]
+    fn content_type_assert_of_media_type() {
+        assert_eq!(
+            Some(assert_query_matches_predicate(
+                &header_query("Content-Type"),
+                PredicateFuncValue::StartWith {
+                    space0: single_space(),
+                    value: PredicateValue::String(simple_template("text/".to_string())),
+                },
+            )),
+            content_type_assert("text/*")
+        );
+        assert_eq!(None, content_type_assert("*/*"));
+    }
+}
@@ -1,5 +1,3 @@
-use std::collections::BTreeMap;
-
 use hurl_core::ast::{Assert, FilterValue, Float, PredicateValue, TemplateElement};
 use log::{debug, warn};
 use oas3::{
@@ -8,14 +6,16 @@ use oas3::{
 };
 
 use crate::{
-    custom_hurl_ast::empty_source_info, hurl_files::single_space,
+    custom_hurl_ast::empty_source_info,
+    hurl_files::single_space,
+    raw_spec::{ordered_properties, RawNode},
     response::common_asserts::assert_query_matches_predicate,
+    schema_ancestors::SchemaAncestors,
+    schema_composition::merge_all_of,
 };
 
 use super::{
-    common_asserts::{
-        assert_query_matches_predicate_with_filters, assert_status_less_than, parse_string_asserts,
-    },
+    common_asserts::{assert_query_matches_predicate_with_filters, parse_string_asserts},
     response_validation::HandleUnionsBy,
 };
 
@@ -42,6 +42,8 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
         &mut self,
         schema: Schema,
         query_value: &hurl_core::ast::QueryValue,
+        raw: RawNode,
+        ancestors: &SchemaAncestors,
     ) -> Result<&Self, RefError> {
         match schema.write_only {
             Some(is_write_only) => {
@@ -52,9 +54,27 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
             None => (),
         }
 
+        // A const is the only valid value, nothing else needs to be asserted
+        if let Some(value) = raw.get("const").and_then(predicate_value) {
+            self.asserts.push(assert_query_matches_predicate(
+                query_value,
+                hurl_core::ast::PredicateFuncValue::Equal {
+                    space0: single_space(),
+                    value,
+                    operator: true,
+                },
+            ));
+            return Ok(self);
+        }
+
         if schema.all_of.len() > 0 {
-            let combined_schema = self.build_schema_from_allof(schema)?;
-            return self.add_asserts_from_schema(combined_schema, query_value);
+            let combined_schema = merge_all_of(schema, self.spec)?;
+            let merged_raw = raw.merged_all_of();
+            let raw = match &merged_raw {
+                Some(value) => raw.with_value(value),
+                None => raw,
+            };
+            return self.add_asserts_from_schema(combined_schema, query_value, raw, ancestors);
         }
 
         let schema_type = match schema.schema_type {
@@ -82,8 +102,12 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
                 oas3::spec::SchemaType::Integer => self.add_int_asserts(schema, query_value),
                 oas3::spec::SchemaType::Number => self.add_number_asserts(schema, query_value),
                 oas3::spec::SchemaType::String => self.add_string_asserts(schema, query_value),
-                oas3::spec::SchemaType::Array => self.add_array_asserts(schema, query_value),
-                oas3::spec::SchemaType::Object => self.add_object_asserts(schema, query_value)?,
+                oas3::spec::SchemaType::Array => {
+                    self.add_array_asserts(schema, query_value, raw, ancestors)?
+                }
+                oas3::spec::SchemaType::Object => {
+                    self.add_object_asserts(schema, query_value, raw, ancestors)?
+                }
                 oas3::spec::SchemaType::Null => (),
             },
             SchemaTypeSet::Multiple(_) => {
@@ -156,27 +180,6 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
         }
     }
 
-    fn build_schema_from_allof(&self, schema: Schema) -> Result<Schema, RefError> {
-        let mut new_schema = schema.clone();
-        let mut props = BTreeMap::new();
-
-        for schema in new_schema.all_of {
-            for prop in schema.resolve(self.spec)?.properties {
-                props.insert(prop.0, prop.1);
-            }
-        }
-
-        for prop in new_schema.properties {
-            props.insert(prop.0, prop.1);
-        }
-
-        new_schema.all_of = vec![];
-
-        new_schema.properties = props;
-
-        Ok(new_schema)
-    }
-
     fn add_int_asserts(&mut self, schema: Schema, query_value: &hurl_core::ast::QueryValue) {
         self.asserts.push(assert_query_matches_predicate(
             &query_value,
@@ -238,12 +241,29 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
         }
     }
 
-    fn add_array_asserts(&mut self, schema: Schema, query_value: &hurl_core::ast::QueryValue) {
+    fn add_array_asserts(
+        &mut self,
+        schema: Schema,
+        query_value: &hurl_core::ast::QueryValue,
+        raw: RawNode,
+        ancestors: &SchemaAncestors,
+    ) -> Result<(), RefError> {
         self.asserts.push(assert_query_matches_predicate(
             &query_value,
             hurl_core::ast::PredicateFuncValue::IsCollection,
         ));
 
+        // Hurl can only check that an array contains a known value
+        if let Some(value) = raw.child("contains").get("const").and_then(predicate_value) {
+            self.asserts.push(assert_query_matches_predicate(
+                query_value,
+                hurl_core::ast::PredicateFuncValue::Include {
+                    space0: single_space(),
+                    value,
+                },
+            ));
+        }
+
         match schema.min_items {
             Some(n) => match n.try_into() {
                 Ok(num) => self
@@ -292,52 +312,93 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
                 Err(e) => {
                     warn!(
                         "maxItems for {} can't be used in hurl it is likely too large {}",
-                        schema.title.unwrap_or("schema".to_string()),
+                        schema.title.clone().unwrap_or("schema".to_string()),
                         e
                     )
                 }
             },
             None => (),
         };
+
+        self.add_tuple_asserts(&schema, query_value, raw, ancestors)
+    }
+
+    /// Asserts the positions of a `prefixItems` tuple. Positions past `minItems` may be missing,
+    /// they're only asserted if optionals are treated as required.
+    fn add_tuple_asserts(
+        &mut self,
+        schema: &Schema,
+        query_value: &hurl_core::ast::QueryValue,
+        raw: RawNode,
+        ancestors: &SchemaAncestors,
+    ) -> Result<(), RefError> {
+        let path = match jsonpath(query_value) {
+            Some(p) => p,
+            None => return Ok(()),
+        };
+        if ancestors.is_at_max_depth() {
+            return Ok(());
+        }
+
+        let asserted = match self.handle_unions_by {
+            HandleUnionsBy::IgnoringThem => schema.min_items.unwrap_or(0) as usize,
+            HandleUnionsBy::TreatingOptionalsAsRequired => usize::MAX,
+        };
+        let prefix_ancestors = ancestors.inline_child();
+        for (i, raw_prefix) in raw.child("prefixItems").elements().into_iter().enumerate() {
+            let prefix_schema = match raw_prefix.schema() {
+                Some(s) if i < asserted => s,
+                _ => break,
+            };
+            self.add_asserts_from_schema(
+                prefix_schema,
+                &hurl_core::ast::QueryValue::Jsonpath {
+                    space0: single_space(),
+                    expr: simple_template(format!("{path}[{i}]")),
+                },
+                raw_prefix,
+                &prefix_ancestors,
+            )?;
+        }
+
+        Ok(())
     }
 
     fn add_object_asserts(
         &mut self,
         schema: Schema,
         query_value: &hurl_core::ast::QueryValue,
+        raw: RawNode,
+        ancestors: &SchemaAncestors,
     ) -> Result<(), RefError> {
         self.asserts.push(assert_query_matches_predicate(
             query_value,
             hurl_core::ast::PredicateFuncValue::IsCollection,
         ));
 
-        let path = match query_value {
-            hurl_core::ast::QueryValue::Jsonpath { space0: _, expr } => {
-                format!(
-                    "{}",
-                    expr.elements
-                        .iter()
-                        .map(|e| match e {
-                            TemplateElement::String { value: _, encoded } => encoded.to_string(),
-                            TemplateElement::Expression(_) => "".to_string(),
-                        })
-                        .collect::<Vec<String>>()
-                        .join(""),
-                )
-            }
-            _ => return Ok(()),
+        let path = match jsonpath(query_value) {
+            Some(p) => p,
+            None => return Ok(()),
         };
 
-        for property in schema.properties {
+        for property in ordered_properties(&schema.properties, raw) {
+            if ancestors.stops_at(property.1) {
+                debug!("Not generating asserts for property at {path}.{}. The property is recursive or nested deeper than {} schemas", property.0, ancestors.max_depth());
+                continue;
+            }
+            let property_ancestors = ancestors.child(property.1);
+
             match self.handle_unions_by {
                 HandleUnionsBy::IgnoringThem => {
-                    if schema.required.contains(&property.0) {
+                    if schema.required.contains(property.0) {
                         let _ = self.add_asserts_from_schema(
                             property.1.resolve(self.spec)?,
                             &hurl_core::ast::QueryValue::Jsonpath {
                                 space0: single_space(),
                                 expr: simple_template(format!("{path}.{}", property.0)),
                             },
+                            raw.property(property.0),
+                            &property_ancestors,
                         );
                     } else {
                         debug!("Not generating asserts for property at {}. The property is not required to generate asserts for optional properties use the option `--validation body-with-optionals`", format!("{path}.{}", property.0));
@@ -350,6 +411,8 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
                             space0: single_space(),
                             expr: simple_template(format!("{path}.{}", property.0)),
                         },
+                        raw.property(property.0),
+                        &property_ancestors,
                     );
                 }
             }
@@ -359,6 +422,35 @@ impl<'a> SchemaToJsonAssertBuilder<'a> {
     }
 }
 
+fn jsonpath(query_value: &hurl_core::ast::QueryValue) -> Option<String> {
+    match query_value {
+        hurl_core::ast::QueryValue::Jsonpath { space0: _, expr } => Some(
+            expr.elements
+                .iter()
+                .map(|e| match e {
+                    TemplateElement::String { value: _, encoded } => encoded.to_string(),
+                    TemplateElement::Expression(_) => "".to_string(),
+                })
+                .collect::<Vec<String>>()
+                .join(""),
+        ),
+        _ => None,
+    }
+}
+
+/// The predicate value of a scalar JSON value, Hurl can't compare collections.
+fn predicate_value(value: &serde_json::Value) -> Option<PredicateValue> {
+    match value {
+        serde_json::Value::Null => Some(PredicateValue::Null),
+        serde_json::Value::Bool(b) => Some(PredicateValue::Bool(*b)),
+        serde_json::Value::Number(n) => Some(predicate_integer_number(n.clone())),
+        serde_json::Value::String(s) => Some(PredicateValue::String(simple_template(
+            s.replace('\\', "\\\\").replace('"', "\\\""),
+        ))),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => None,
+    }
+}
+
 fn serde_num_to_hurl_num(n: serde_json::Number) -> hurl_core::ast::PredicateValue {
     hurl_core::ast::PredicateValue::Number(hurl_core::ast::Number::Float(Float {
         value: n.to_string().parse::<f64>().unwrap_or(0.0),
@@ -366,7 +458,7 @@ fn serde_num_to_hurl_num(n: serde_json::Number) -> hurl_core::ast::PredicateValu
     }))
 }
 
-fn simple_template(element: String) -> hurl_core::ast::Template {
+pub fn simple_template(element: String) -> hurl_core::ast::Template {
     hurl_core::ast::Template {
         delimiter: Some('"'),
         source_info: empty_source_info(),
@@ -377,7 +469,7 @@ fn simple_template(element: String) -> hurl_core::ast::Template {
     }
 }
 
-fn predicate_integer_number(n: serde_json::Number) -> PredicateValue {
+pub fn predicate_integer_number(n: serde_json::Number) -> PredicateValue {
     match n.to_string().parse::<i64>() {
         Ok(num) => hurl_core::ast::PredicateValue::Number(hurl_core::ast::Number::Integer(num)),
         // Fallback to float if not int
@@ -387,16 +479,12 @@ fn predicate_integer_number(n: serde_json::Number) -> PredicateValue {
 
 pub fn parse_json_response_body_asserts(
     schema: Schema,
+    raw: RawNode,
+    ancestors: &SchemaAncestors,
     spec: &Spec,
     handle_unions_by: HandleUnionsBy,
-    should_validate_less_than_400: bool,
+    mut asserts: Vec<Assert>,
 ) -> Result<Vec<Assert>, RefError> {
-    let mut asserts = vec![];
-
-    if should_validate_less_than_400 {
-        asserts.push(assert_status_less_than(400))
-    }
-
     Ok(
         SchemaToJsonAssertBuilder::new(&mut asserts, spec, &handle_unions_by)
             .add_asserts_from_schema(
@@ -405,6 +493,8 @@ pub fn parse_json_response_body_asserts(
                     space0: single_space(),
                     expr: simple_template("$".to_string()),
                 },
+                raw,
+                ancestors,
             )?
             .get_asserts(),
     )
@@ -429,14 +519,24 @@ mod tests {
     };
 
     use super::parse_json_response_body_asserts;
+    use crate::{
+        raw_spec::RawSpec,
+        schema_ancestors::{SchemaAncestors, DEFAULT_MAX_DEPTH},
+    };
 
     #[test]
     fn parse_json_response_body_with_no_schema_type_returns_empty_asserts() {
         let mut schema = Schema::default();
         let spec = get_default_spec();
         schema.schema_type = None;
-        let result =
-            parse_json_response_body_asserts(schema, &spec, HandleUnionsBy::IgnoringThem, true);
+        let result = parse_json_response_body_asserts(
+            schema,
+            RawSpec::default().root(),
+            &SchemaAncestors::root(DEFAULT_MAX_DEPTH),
+            &spec,
+            HandleUnionsBy::IgnoringThem,
+            vec![assert_status_less_than(400)],
+        );
         let expected: Vec<Assert> = vec![assert_status_less_than(400)];
 
         assert_eq!(Ok(expected), result);
@@ -451,9 +551,11 @@ mod tests {
 
         let result = parse_json_response_body_asserts(
             schema,
+            RawSpec::default().root(),
+            &SchemaAncestors::root(DEFAULT_MAX_DEPTH),
             &get_default_spec(),
             HandleUnionsBy::IgnoringThem,
-            true,
+            vec![assert_status_less_than(400)],
         );
 
         let expected: Vec<Assert> = vec![
@@ -483,9 +585,11 @@ mod tests {
 
         let result = parse_json_response_body_asserts(
             schema,
+            RawSpec::default().root(),
+            &SchemaAncestors::root(DEFAULT_MAX_DEPTH),
             &get_default_spec(),
             HandleUnionsBy::IgnoringThem,
-            true,
+            vec![assert_status_less_than(400)],
         );
 
         let expected: Vec<Assert> = vec![
@@ -547,9 +651,11 @@ mod tests {
 
         let result = parse_json_response_body_asserts(
             schema,
+            RawSpec::default().root(),
+            &SchemaAncestors::root(DEFAULT_MAX_DEPTH),
             &get_default_spec(),
             HandleUnionsBy::IgnoringThem,
-            true,
+            vec![assert_status_less_than(400)],
         );
 
         let expected: Vec<Assert> = vec![
@@ -1,3 +1,4 @@
 mod common_asserts;
+mod header_asserts;
 mod json_asserts;
 pub mod response_validation;
@@ -1,74 +1,105 @@
-use super::json_asserts::parse_json_response_body_asserts;
-use crate::response::common_asserts::{assert_status_less_than, parse_string_asserts};
-use std::{collections::BTreeMap, vec};
+use super::{
+    header_asserts::{content_type_assert, parse_response_header_asserts},
+    json_asserts::parse_json_response_body_asserts,
+};
+use crate::response::common_asserts::{
+    assert_query_matches_predicate, assert_status_less_than, parse_string_asserts,
+};
+use std::vec;
 
 use hurl_core::ast::{
-    Assert, LineTerminator, Response, Section, Status, Version, VersionValue, Whitespace,
+    Assert, Number, PredicateFuncValue, PredicateValue, Response, Section, Status, StatusValue,
+    Version, VersionValue, Whitespace,
 };
-use log::{trace, warn};
+use log::{debug, trace, warn};
 use oas3::{
     spec::{Operation, RefError},
     Schema, Spec,
 };
 
 use crate::{
-    content_type::ContentType,
+    content_type::{preferred_content, ContentType},
     custom_hurl_ast::{empty_source_info, empty_space, newline},
+    raw_spec::RawNode,
+    schema_ancestors::{SchemaAncestors, DEFAULT_MAX_DEPTH},
+    settings::Settings,
 };
 
+/// The key of the response documented for any status code without a response of its own.
+const DEFAULT_RESPONSE: &str = "default";
+
 pub enum HandleUnionsBy {
     IgnoringThem,
     TreatingOptionalsAsRequired,
 }
 
-pub fn validate_response_not_error() -> Response {
-    response_structure(vec![Section {
-        line_terminators: vec![],
-        space0: empty_space(),
-        line_terminator0: LineTerminator {
-            space0: empty_space(),
-            comment: None,
-            newline: empty_space(),
-        },
-        value: hurl_core::ast::SectionValue::Asserts(vec![assert_status_less_than(400)]),
-        source_info: empty_source_info(),
-    }])
+/// Validates the status of the response against the documented status codes, see
+/// `selected_response`.
+pub fn validate_response_status(
+    operation: &Operation,
+    spec: &Spec,
+    response_key: Option<&str>,
+) -> Result<Option<Response>, RefError> {
+    Ok(
+        selected_response(operation, spec, response_key)?.map(|expected| {
+            let mut status = expected.status;
+            // Only error responses are documented, the request is still expected to succeed
+            if response_key.is_none()
+                && status.code == StatusValue::Any
+                && status.asserts.is_empty()
+            {
+                status.asserts = vec![assert_status_less_than(400)];
+            }
+            response_structure(status.code, asserts_section(status.asserts))
+        }),
+    )
 }
 
 pub fn validation_response_full(
     operation: &Operation,
+    raw_operation: RawNode,
     spec: &Spec,
-    content_type: &ContentType,
+    settings: &Settings,
     handle_unions_by: HandleUnionsBy,
+    response_key: Option<&str>,
 ) -> Result<Option<Response>, RefError> {
+    let content_type = &settings.content_type;
     let operation_id = operation
         .operation_id
         .clone()
         .unwrap_or("operationWithNoId".to_string());
 
-    let mut has_code_less_than_400 = false;
-
-    let response_btree_map = &operation.clone().responses.unwrap_or(BTreeMap::new());
-
-    let response = match response_btree_map
-        .iter()
-        .find(|kv| kv.0.chars().nth(0).unwrap_or('6').to_digit(10).unwrap_or(6) < 4)
-    {
-        Some(r) => {
-            has_code_less_than_400 = true;
-            r.1.resolve(spec)?
-        }
-        None => match response_btree_map.iter().nth(0) {
-            Some(r) => r.1.resolve(spec)?,
-            None => return Ok(None),
-        },
+    let ExpectedResponse {
+        key,
+        response,
+        status,
+        validate_body,
+    } = match selected_response(operation, spec, response_key)? {
+        Some(r) => r,
+        None => return Ok(None),
     };
+    if !validate_body {
+        return Ok(Some(response_structure(
+            status.code,
+            asserts_section(status.asserts),
+        )));
+    }
+    let raw_response = raw_operation.child("responses").child(&key);
+    let mut asserts = status.asserts;
+    asserts.extend(parse_response_header_asserts(
+        &response.headers,
+        raw_response.child("headers"),
+        spec,
+        &handle_unions_by,
+    )?);
+    let headers_only = response_structure(status.code.clone(), asserts_section(asserts.clone()));
 
-    let content = match response
-        .content
-        .iter()
-        .find(|c| content_type.matches_string(c.0))
-    {
+    let content = match preferred_content(&response.content, &settings.media_types).or_else(|| {
+        response
+            .content
+            .iter()
+            .find(|c| content_type.matches_string(c.0))
+    }) {
         Some(c) => c,
         None => match response
             .content
@@ -81,60 +112,296 @@ pub fn validation_response_full(
             }
             None => {
                 warn!("operation {operation_id} does not have any of the supported content types ({}). Defaulting to an empty response body", ContentType::supported_types().join(", "));
-                return Ok(None);
+                return Ok(Some(headers_only));
             }
         },
     };
 
+    asserts.extend(content_type_assert(content.0));
+    let status_only = response_structure(status.code.clone(), asserts_section(asserts.clone()));
+
     let content_type = match ContentType::from_string(content.0) {
         Ok(ct) => ct,
         Err(_) => {
             warn!("operation {operation_id} does not have any of the supported content types ({}). Defaulting to an empty request body", ContentType::supported_types().join(", "));
-            return Ok(None);
+            return Ok(Some(status_only));
         }
     };
 
-    let schema = match &content.1.schema {
-        Some(s) => s.resolve(spec)?,
-        None => return Ok(None),
+    let schema_ref = match &content.1.schema {
+        Some(s) => s,
+        None => return Ok(Some(status_only)),
     };
+    let schema = schema_ref.resolve(spec)?;
+    let raw_schema = raw_response
+        .child("content")
+        .child(content.0)
+        .child("schema");
+    let root = SchemaAncestors::root(settings.max_depth.unwrap_or(DEFAULT_MAX_DEPTH));
 
     match content_type {
-        ContentType::Text => Ok(Some(response_structure(vec![Section {
-            line_terminators: vec![],
-            space0: empty_space(),
-            line_terminator0: newline(),
-            value: hurl_core::ast::SectionValue::Asserts(parse_plain_text_response_body(
-                schema,
-                has_code_less_than_400,
-            )?),
-            source_info: empty_source_info(),
-        }]))),
-        ContentType::Json => Ok(Some(response_structure(vec![Section {
-            line_terminators: vec![],
-            space0: empty_space(),
-            line_terminator0: newline(),
-            value: hurl_core::ast::SectionValue::Asserts(parse_json_response_body_asserts(
+        ContentType::Text => Ok(Some(response_structure(
+            status.code,
+            asserts_section(parse_plain_text_response_body(schema, asserts)?),
+        ))),
+        ContentType::Json => Ok(Some(response_structure(
+            status.code,
+            asserts_section(parse_json_response_body_asserts(
                 schema,
+                raw_schema,
+                &root.child(schema_ref),
                 &spec,
                 handle_unions_by,
-                has_code_less_than_400,
+                asserts,
             )?),
-            source_info: empty_source_info(),
-        }]))),
+        ))),
+        ContentType::MultipartFormData
+        | ContentType::FormUrlEncoded
+        | ContentType::Xml
+        | ContentType::Binary => {
+            warn!("operation {operation_id} responds with {}, this tool can't validate that content type. Only validating the status", content.0);
+            Ok(Some(status_only))
+        }
+    }
+}
+
+/// The media type to accept in the response, the one its body would be validated against. If
+/// this tool supports none of the response's media types all of them are accepted.
+pub fn accepted_media_type(
+    operation: &Operation,
+    spec: &Spec,
+    content_type: &ContentType,
+    media_types: &[String],
+    response_key: Option<&str>,
+) -> Result<Option<String>, RefError> {
+    let response = match selected_response(operation, spec, response_key)? {
+        Some(r) => r.response,
+        None => return Ok(None),
+    };
+
+    if response.content.is_empty() {
+        return Ok(None);
+    }
+
+    let selected = preferred_content(&response.content, media_types)
+        .or_else(|| {
+            response
+                .content
+                .iter()
+                .find(|c| content_type.matches_string(c.0))
+        })
+        .or_else(|| {
+            response
+                .content
+                .iter()
+                .find(|c| ContentType::is_supported(c.0))
+        });
+
+    Ok(Some(match selected {
+        Some(c) => c.0.to_string(),
+        None => response
+            .content
+            .keys()
+            .cloned()
+            .collect::<Vec<String>>()
+            .join(", "),
+    }))
+}
+
+/// The keys of the operation's responses that are status codes or ranges, e.g. `404` or `2XX`.
+pub fn documented_status_codes(operation: &Operation) -> Vec<String> {
+    operation
+        .responses
+        .iter()
+        .flat_map(|responses| responses.keys())
+        .filter(|key| status_range(key).is_some())
+        .cloned()
+        .collect()
+}
+
+/// The response documented under the key, e.g. `404`, or the expected success response if no
+/// key is given, see `expected_response`.
+fn selected_response(
+    operation: &Operation,
+    spec: &Spec,
+    response_key: Option<&str>,
+) -> Result<Option<ExpectedResponse>, RefError> {
+    let key = match response_key {
+        Some(k) => k,
+        None => return expected_response(operation, spec),
+    };
+
+    let response = match operation.responses.as_ref().and_then(|r| r.get(key)) {
+        Some(r) => r.resolve(spec)?,
+        None => {
+            warn!(
+                "operation {} doesn't document a {key} response, not validating the response",
+                operation
+                    .operation_id
+                    .clone()
+                    .unwrap_or("operationWithNoId".to_string())
+            );
+            return Ok(None);
+        }
+    };
+
+    let status = match status_range(key) {
+        Some((min, max)) if min == max => ExpectedStatus {
+            code: StatusValue::Specific(min),
+            asserts: vec![],
+        },
+        Some((min, max)) => ExpectedStatus {
+            code: StatusValue::Any,
+            asserts: status_between(min, max),
+        },
+        None => ExpectedStatus {
+            code: StatusValue::Any,
+            asserts: vec![],
+        },
+    };
+
+    Ok(Some(ExpectedResponse {
+        key: key.to_string(),
+        response,
+        status,
+        validate_body: true,
+    }))
+}
+
+/// The response a successful request is expected to get and the key it's documented under.
+struct ExpectedResponse {
+    key: String,
+    response: oas3::spec::Response,
+    status: ExpectedStatus,
+    /// Whether the body and headers can be validated, they can't if the expected statuses
+    /// document different ones.
+    validate_body: bool,
+}
+
+/// How the status of a response is validated.
+struct ExpectedStatus {
+    /// The status on the response line, any status if several are expected.
+    code: StatusValue,
+    asserts: Vec<Assert>,
+}
+
+/// The documented success response, the one with the lowest `2XX` status code. If there's none
+/// the one with the lowest status code below 400, then the `default` response, then the first
+/// error response. The body of that response is validated.
+///
+/// A single success code is expected on the response line, e.g. `HTTP 201`. If there are several
+/// success codes or a range like `2XX` the status is asserted to be within them instead, the body
+/// is only validated if they all document the same one. A `default` response can have any
+/// status, a successful one is asserted. An error response is only documented for its body, its
+/// status isn't validated.
+fn expected_response(
+    operation: &Operation,
+    spec: &Spec,
+) -> Result<Option<ExpectedResponse>, RefError> {
+    let responses = operation.responses.clone().unwrap_or_default();
+    let ranges: Vec<(&String, (u64, u64))> = responses
+        .keys()
+        .filter_map(|key| status_range(key).map(|range| (key, range)))
+        .collect();
+
+    let success: Vec<&(&String, (u64, u64))> = ranges
+        .iter()
+        .filter(|r| (200..300).contains(&r.1 .0))
+        .collect();
+    let expected = match success.is_empty() {
+        true => ranges.iter().filter(|r| r.1 .0 < 400).collect(),
+        false => success,
+    };
+
+    let (key, status) = match expected.as_slice() {
+        [] => match responses.contains_key(DEFAULT_RESPONSE) {
+            true => (
+                DEFAULT_RESPONSE,
+                ExpectedStatus {
+                    code: StatusValue::Any,
+                    asserts: vec![assert_status_less_than(400)],
+                },
+            ),
+            false => match responses.keys().next() {
+                Some(key) => (
+                    key.as_str(),
+                    ExpectedStatus {
+                        code: StatusValue::Any,
+                        asserts: vec![],
+                    },
+                ),
+                None => return Ok(None),
+            },
+        },
+        [(key, (min, max))] if min == max => (
+            key.as_str(),
+            ExpectedStatus {
+                code: StatusValue::Specific(*min),
+                asserts: vec![],
+            },
+        ),
+        ranges => {
+            let min = ranges.iter().map(|r| r.1 .0).min().unwrap_or(0);
+            let max = ranges.iter().map(|r| r.1 .1).max().unwrap_or(0);
+            (
+                ranges[0].0.as_str(),
+                ExpectedStatus {
+                    code: StatusValue::Any,
+                    asserts: status_between(min, max),
+                },
+            )
+        }
+    };
+
+    let response = match responses.get(key) {
+        Some(r) => r.resolve(spec)?,
+        None => return Ok(None),
+    };
+
+    // Any of several statuses can be returned, the body is only known if they all share it
+    let mut validate_body = true;
+    for (other_key, _) in expected.iter().skip(1) {
+        let other = responses[other_key.as_str()].resolve(spec)?;
+        if other.content != response.content || other.headers != response.headers {
+            debug!(
+                "The {key} and {other_key} responses of operation {} differ, only validating the status",
+                operation.operation_id.as_deref().unwrap_or_default()
+            );
+            validate_body = false;
+        }
+    }
+
+    Ok(Some(ExpectedResponse {
+        key: key.to_string(),
+        response,
+        status,
+        validate_body,
+    }))
+}
+
+/// The lowest and highest status code of a response key, e.g. `(201, 201)` for `201` and
+/// `(200, 299)` for `2XX`. `None` for the `default` key and keys that aren't status codes.
+fn status_range(key: &str) -> Option<(u64, u64)> {
+    if key.len() != 3 || !key.is_ascii() {
+        return None;
+    }
+
+    if let Ok(code) = key.parse::<u64>() {
+        return Some((code, code));
+    }
+
+    let class = key[..1].parse::<u64>().ok()?;
+    match key[1..].eq_ignore_ascii_case("XX") && (1..=5).contains(&class) {
+        true => Some((class * 100, class * 100 + 99)),
+        false => None,
     }
 }
 
 fn parse_plain_text_response_body(
     schema: Schema,
-    should_validate_less_than_400: bool,
+    mut asserts: Vec<Assert>,
 ) -> Result<Vec<Assert>, RefError> {
     trace!("parsing plain text request body");
-    let mut asserts = vec![];
-
-    if should_validate_less_than_400 {
-        asserts.push(assert_status_less_than(400))
-    }
 
     asserts.extend(parse_string_asserts(
         schema,
@@ -144,6 +411,25 @@ fn parse_plain_text_response_body(
     Ok(asserts)
 }
 
+fn status_between(min: u64, max: u64) -> Vec<Assert> {
+    vec![
+        assert_status(PredicateFuncValue::GreaterThanOrEqual {
+            space0: single_space(),
+            value: PredicateValue::Number(Number::Integer(min as i64)),
+            operator: true,
+        }),
+        assert_status(PredicateFuncValue::LessThanOrEqual {
+            space0: single_space(),
+            value: PredicateValue::Number(Number::Integer(max as i64)),
+            operator: true,
+        }),
+    ]
+}
+
+fn assert_status(predicate: PredicateFuncValue) -> Assert {
+    assert_query_matches_predicate(&hurl_core::ast::QueryValue::Status, predicate)
+}
+
 fn single_space() -> Whitespace {
     Whitespace {
         value: " ".to_string(),
@@ -151,7 +437,22 @@ fn single_space() -> Whitespace {
     }
 }
 
-fn response_structure(sections: Vec<Section>) -> Response {
+/// An `[Asserts]` section, none if there's nothing to assert.
+fn asserts_section(asserts: Vec<Assert>) -> Vec<Section> {
+    if asserts.is_empty() {
+        return vec![];
+    }
+
+    vec![Section {
+        line_terminators: vec![],
+        space0: empty_space(),
+        line_terminator0: newline(),
+        value: hurl_core::ast::SectionValue::Asserts(asserts),
+        source_info: empty_source_info(),
+    }]
+}
+
+fn response_structure(status: StatusValue, sections: Vec<Section>) -> Response {
     Response {
         line_terminators: vec![newline()],
         version: Version {
@@ -160,7 +461,7 @@ fn response_structure(sections: Vec<Section>) -> Response {
         },
         space0: empty_space(),
         status: Status {
-            value: hurl_core::ast::StatusValue::Any,
+            value: status,
             source_info: empty_source_info(),
         },
         space1: single_space(),
@@ -171,3 +472,162 @@ fn response_structure(sections: Vec<Section>) -> Response {
         source_info: empty_source_info(),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use hurl_core::ast::{Assert, StatusValue};
+    use oas3::spec::Operation;
+    use pretty_assertions::assert_eq;
+
+    use super::{
+        documented_status_codes, expected_response, selected_response, status_between, status_range,
+    };
+    use crate::response::common_asserts::assert_status_less_than;
+
+    fn operation(response_keys: &[&str]) -> Operation {
+        let responses: serde_json::Map<String, serde_json::Value> = response_keys
+            .iter()
+            .map(|key| (key.to_string(), serde_json::json!({"description": key})))
+            .collect();
+
+        serde_json::from_value(serde_json::json!({ "responses": responses })).unwrap()
+    }
+
+    fn expected(response_keys: &[&str]) -> (String, StatusValue, Vec<Assert>) {
+        let spec = oas3::from_str(
+            r#"{"openapi": "3.1.0", "info": {"title": "Test", "version": "1.0.0"}, "paths": {}}"#,
+        )
+        .unwrap();
+        let expected = expected_response(&operation(response_keys), &spec)
+            .unwrap()
+            .unwrap();
+
+        (expected.key, expected.status.code, expected.status.asserts)
+    }
+
+    #[test]
+    fn status_range_of_response_keys() {
+        assert_eq!(Some((201, 201)), status_range("201"));
+        assert_eq!(Some((200, 299)), status_range("2XX"));
+        assert_eq!(Some((400, 499)), status_range("4xx"));
+        assert_eq!(None, status_range("default"));
+        assert_eq!(None, status_range("9XX"));
+        assert_eq!(None, status_range("é1"));
+    }
+
+    #[test]
+    fn expected_response_with_a_single_success_code() {
+        assert_eq!(
+            ("201".to_string(), StatusValue::Specific(201), vec![]),
+            expected(&["201", "400", "default"])
+        );
+    }
+
+    #[test]
+    fn expected_response_with_several_success_codes_or_ranges() {
+        assert_eq!(
+            (
+                "200".to_string(),
+                StatusValue::Any,
+                status_between(200, 204)
+            ),
+            expected(&["200", "204", "404"])
+        );
+        assert_eq!(
+            (
+                "2XX".to_string(),
+                StatusValue::Any,
+                status_between(200, 299)
+            ),
+            expected(&["2XX", "default"])
+        );
+    }
+
+    #[test]
+    fn expected_response_without_success_codes() {
+        assert_eq!(
+            ("304".to_string(), StatusValue::Specific(304), vec![]),
+            expected(&["304", "404"])
+        );
+        assert_eq!(
+            (
+                "default".to_string(),
+                StatusValue::Any,
+                vec![assert_status_less_than(400)]
+            ),
+            expected(&["404", "default"])
+        );
+        assert_eq!(
+            ("404".to_string(), StatusValue::Any, vec![]),
+            expected(&["404", "500"])
+        );
+    }
+
+    #[test]
+    fn expected_response_validates_body_only_if_statuses_share_it() {
+        let spec = oas3::from_str(
+            r#"{"openapi": "3.1.0", "info": {"title": "Test", "version": "1.0.0"}, "paths": {}}"#,
+        )
+        .unwrap();
+        let json = serde_json::json!({
+            "description": "A pet",
+            "content": {"application/json": {"schema": {"type": "object"}}}
+        });
+        let operation = |responses: serde_json::Value| -> Operation {
+            serde_json::from_value(serde_json::json!({ "responses": responses })).unwrap()
+        };
+        let validate_body = |operation: Operation| {
+            expected_response(&operation, &spec)
+                .unwrap()
+                .unwrap()
+                .validate_body
+        };
+
+        assert_eq!(
+            true,
+            validate_body(operation(serde_json::json!({"200": json, "201": json})))
+        );
+        assert_eq!(
+            false,
+            validate_body(operation(
+                serde_json::json!({"200": json, "204": {"description": "No content"}})
+            ))
+        );
+    }
+
+    #[test]
+    fn selected_response_by_key() {
+        let spec = oas3::from_str(
+            r#"{"openapi": "3.1.0", "info": {"title": "Test", "version": "1.0.0"}, "paths": {}}"#,
+        )
+        .unwrap();
+        let operation = operation(&["201", "4XX", "404", "default"]);
+        let selected = |key| {
+            selected_response(&operation, &spec, Some(key))
+                .unwrap()
+                .map(|r| (r.key, r.status.code, r.status.asserts))
+        };
+
+        assert_eq!(
+            Some(("404".to_string(), StatusValue::Specific(404), vec![])),
+            selected("404")
+        );
+        assert_eq!(
+            Some((
+                "4XX".to_string(),
+                StatusValue::Any,
+                status_between(400, 499)
+            )),
+            selected("4XX")
+        );
+        assert_eq!(
+            Some(("default".to_string(), StatusValue::Any, vec![])),
+            selected("default")
+        );
+        assert_eq!(None, selected("500"));
+        assert_eq!(
+            vec!["201", "404", "4XX"],
+            documented_status_codes(&operation)
+        );
+    }
+}
@@ -0,0 +1,2 @@
+mod pattern_string;
+pub mod values;
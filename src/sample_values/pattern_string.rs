@@ -0,0 +1,202 @@
+use fastrand::Rng;
+use regex::Regex;
+use regex_syntax::hir::{Class, Hir, HirKind};
+
+/// Characters preferred when a character class allows them, so generated strings stay readable.
+/// They're all unreserved in URLs, so strings used as path or query parameters need no encoding.
+const PREFERRED_CHARS: [char; 7] = ['a', 'A', '0', '-', '_', '.', '~'];
+
+/// Builds a string matching the pattern. Repetitions are repeated as few times as possible, more
+/// only when that's needed to reach the minimum length. With a random number generator the
+/// characters and repetitions are random.
+pub fn string_from_pattern(
+    pattern: &str,
+    min_length: Option<u64>,
+    max_length: Option<u64>,
+    mut rng: Option<&mut Rng>,
+) -> Result<String, String> {
+    let hir = regex_syntax::parse(pattern).map_err(|e| format!("unsupported pattern: {e}"))?;
+    let min_length = min_length.unwrap_or(0) as usize;
+
+    let mut value = generate(&hir, 0, &mut rng)?;
+    let mut extra_repetitions = 1;
+    while value.chars().count() < min_length && extra_repetitions <= min_length {
+        value = generate(&hir, extra_repetitions, &mut rng)?;
+        extra_repetitions += 1;
+    }
+
+    let length = value.chars().count();
+    if length < min_length || max_length.is_some_and(|max| length > max as usize) {
+        return Err("couldn't fit the minLength and maxLength of the schema".to_string());
+    }
+
+    // The pattern was parsed so this can only fail if it's too big to compile
+    let matches = Regex::new(pattern)
+        .map(|regex| regex.is_match(&value))
+        .unwrap_or(false);
+    if !matches {
+        return Err("the generated string doesn't match it".to_string());
+    }
+
+    Ok(value)
+}
+
+fn generate(
+    hir: &Hir,
+    extra_repetitions: usize,
+    rng: &mut Option<&mut Rng>,
+) -> Result<String, String> {
+    match hir.kind() {
+        HirKind::Empty | HirKind::Look(_) => Ok("".to_string()),
+        HirKind::Literal(literal) => String::from_utf8(literal.0.to_vec())
+            .map_err(|_| "the pattern contains a literal that isn't UTF-8".to_string()),
+        HirKind::Class(class) => class_char(class, rng).map(|c| c.to_string()),
+        HirKind::Repetition(repetition) => {
+            let min = repetition.min as usize + rng.as_mut().map_or(0, |rng| rng.usize(..=2));
+            let count = match repetition.max {
+                Some(max) => (min + extra_repetitions).min(max as usize),
+                None => min + extra_repetitions,
+            };
+
+            (0..count)
+                .map(|_| generate(&repetition.sub, extra_repetitions, rng))
+                .collect()
+        }
+        HirKind::Capture(capture) => generate(&capture.sub, extra_repetitions, rng),
+        HirKind::Concat(hirs) => hirs
+            .iter()
+            .map(|h| generate(h, extra_repetitions, rng))
+            .collect(),
+        HirKind::Alternation(hirs) => {
+            let index = rng.as_mut().map_or(0, |rng| rng.usize(..hirs.len()));
+            match hirs.get(index) {
+                Some(h) => generate(h, extra_repetitions, rng),
+                None => Ok("".to_string()),
+            }
+        }
+    }
+}
+
+fn class_char(class: &Class, rng: &mut Option<&mut Rng>) -> Result<char, String> {
+    let ranges: Vec<(char, char)> = match class {
+        Class::Unicode(class) => class
+            .ranges()
+            .iter()
+            .map(|r| (r.start(), r.end()))
+            .collect(),
+        Class::Bytes(class) => class
+            .ranges()
+            .iter()
+            .map(|r| (r.start() as char, r.end() as char))
+            .collect(),
+    };
+
+    let in_class = |c: &char| ranges.iter().any(|r| r.0 <= *c && *c <= r.1);
+
+    // Random characters are limited to printable ASCII to keep them readable, preferably the ones
+    // unreserved in URLs
+    if let Some(rng) = rng {
+        let printable: Vec<char> = (' '..='~').filter(in_class).collect();
+        let unreserved: Vec<char> = printable
+            .iter()
+            .copied()
+            .filter(|c| is_unreserved(*c))
+            .collect();
+        let candidates = if unreserved.is_empty() {
+            printable
+        } else {
+            unreserved
+        };
+        if !candidates.is_empty() {
+            return Ok(candidates[rng.usize(..candidates.len())]);
+        }
+    }
+
+    if let Some(c) = PREFERRED_CHARS.iter().find(|c| in_class(c)) {
+        return Ok(*c);
+    }
+
+    ranges
+        .iter()
+        .flat_map(|r| r.0..=r.1)
+        .find(|c| !c.is_control())
+        .ok_or_else(|| "the pattern contains a class of only control characters".to_string())
+}
+
+fn is_unreserved(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~')
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::string_from_pattern;
+
+    #[test]
+    fn string_from_pattern_matches_pattern() {
+        assert_eq!(
+            Ok("ORD-00000000".to_string()),
+            string_from_pattern("^ORD-[0-9]{8}$", None, None, None)
+        );
+        assert_eq!(
+            Ok("a@a.aa".to_string()),
+            string_from_pattern(r"^\w+@[a-z]+\.[a-z]{2,3}$", None, None, None)
+        );
+        assert_eq!(
+            Ok("cat".to_string()),
+            string_from_pattern("^(cat|dog)s?$", None, None, None)
+        );
+    }
+
+    #[test]
+    fn string_from_pattern_fits_min_length() {
+        assert_eq!(
+            Ok("Aaaaa".to_string()),
+            string_from_pattern("^[A-Z][a-z]*$", Some(5), None, None)
+        );
+        assert!(string_from_pattern("^[A-Z]{2}$", Some(5), None, None).is_err());
+        assert!(string_from_pattern("^[A-Z]{6}$", None, Some(5), None).is_err());
+    }
+
+    #[test]
+    fn string_from_pattern_fails_on_unsupported_constructs() {
+        assert!(string_from_pattern(r"^(?=a)\w+$", None, None, None).is_err());
+        assert!(string_from_pattern(r"^(a)\1$", None, None, None).is_err());
+    }
+
+    #[test]
+    fn string_from_pattern_with_rng_matches_pattern() {
+        let pattern = r"^[A-Z]{3}-(cat|dog)-\d+$";
+        let regex = regex::Regex::new(pattern).unwrap();
+        let mut rng = fastrand::Rng::with_seed(7);
+
+        for _ in 0..20 {
+            let value = string_from_pattern(pattern, None, None, Some(&mut rng)).unwrap();
+            assert!(regex.is_match(&value), "{value} doesn't match {pattern}");
+        }
+    }
+
+    #[test]
+    fn string_from_pattern_prefers_characters_unreserved_in_urls() {
+        assert_eq!(
+            Ok(".".to_string()),
+            string_from_pattern("^[ #.]$", None, None, None)
+        );
+        assert_eq!(
+            Ok("#".to_string()),
+            string_from_pattern("^[#?]$", None, None, None)
+        );
+
+        let mut rng = fastrand::Rng::with_seed(7);
+        for _ in 0..20 {
+            let value = string_from_pattern("^[ -~]{10}$", None, None, Some(&mut rng)).unwrap();
+            assert!(
+                value
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "-._~".contains(c)),
+                "{value} contains reserved characters"
+            );
+        }
+    }
+}
@@ -0,0 +1,718 @@
+//! Sample values for generated requests. Shared by request bodies and path and query params so
+//! the same schema produces the same value wherever it's used.
+
+use std::cell::RefCell;
+
+use fastrand::Rng;
+use log::warn;
+use oas3::Schema;
+
+use super::pattern_string::string_from_pattern;
+use crate::raw_spec::RawNode;
+
+/// Hurl's generator of a new UUID for every run, see https://hurl.dev/docs/templates.html
+pub const NEW_UUID: &str = "{{newUuid}}";
+const UUID_LENGTH: usize = 36;
+const RANDOM_STRING_LENGTH: u64 = 8;
+/// How far inside an exclusive bound numbers are placed, unless the bounds are closer.
+const NUMBER_STEP: f64 = 0.5;
+/// How far past the lower bound, or zero, random numbers can be when there's no upper bound.
+const RANDOM_NUMBER_RANGE: f64 = 1000.0;
+const LOWERCASE_ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+const BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Generates the sample values. By default the values are always the same, in random mode they
+/// vary but are reproducible with the same seed.
+#[derive(Default)]
+pub struct SampleValues {
+    rng: Option<RefCell<Rng>>,
+    seed: Option<u64>,
+    dynamic: bool,
+}
+
+impl SampleValues {
+    pub fn random(seed: u64) -> Self {
+        Self {
+            rng: Some(RefCell::new(Rng::with_seed(seed))),
+            seed: Some(seed),
+            dynamic: false,
+        }
+    }
+
+    /// Values that have to be unique are generated by hurl when the request runs.
+    pub fn with_dynamic_values(self, dynamic: bool) -> Self {
+        Self { dynamic, ..self }
+    }
+
+    /// A string hurl generates when the request runs, so it's different on every run. Only for
+    /// formats that are usually unique, e.g. `uuid` or `email`.
+    pub fn dynamic_string(&self, schema: &Schema) -> Option<String> {
+        // A generated string would never match the pattern
+        if !self.dynamic || schema.pattern.is_some() {
+            return None;
+        }
+
+        let value = match schema.format.as_deref()? {
+            "uuid" => NEW_UUID.to_string(),
+            "email" => format!("user-{NEW_UUID}@example.com"),
+            "uri" | "url" => format!("https://example.com/{NEW_UUID}"),
+            "hostname" => format!("{NEW_UUID}.example.com"),
+            _ => return None,
+        };
+
+        let length = value.len() - NEW_UUID.len() + UUID_LENGTH;
+        if schema.min_length.is_some_and(|min| length < min as usize)
+            || schema.max_length.is_some_and(|max| length > max as usize)
+        {
+            return None;
+        }
+
+        Some(value)
+    }
+
+    /// Random values from another seed, dynamic values are kept.
+    pub fn reseeded(&self, seed: u64) -> Self {
+        Self::random(seed).with_dynamic_values(self.dynamic)
+    }
+
+    /// The seed of the random values, `None` if the values aren't random.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
+    /// A string hurl generates if dynamic values are used, see `dynamic_string`. Otherwise a
+    /// string matching the schema's `pattern`. Without a pattern, a string satisfying the
+    /// schema's `format`, or the default when the format isn't known. The string is repeated or
+    /// cut to fit within `minLength` and `maxLength`.
+    pub fn string_from_schema(&self, schema: &Schema, default: &str) -> String {
+        if let Some(value) = self.dynamic_string(schema) {
+            return value;
+        }
+
+        if let Some(pattern) = &schema.pattern {
+            let mut rng = self.rng.as_ref().map(|rng| rng.borrow_mut());
+            match string_from_pattern(
+                pattern,
+                schema.min_length,
+                schema.max_length,
+                rng.as_deref_mut(),
+            ) {
+                Ok(value) => return value,
+                Err(e) => warn!("couldn't generate a string for the pattern `{pattern}`, {e}. Defaulting to a string that might not match it"),
+            }
+        }
+
+        let format = schema.format.as_deref();
+        let value = match &self.rng {
+            Some(rng) => {
+                let rng = &mut rng.borrow_mut();
+                match format.and_then(|f| random_string_from_format(f, rng)) {
+                    Some(value) => value,
+                    None => {
+                        let length = RANDOM_STRING_LENGTH
+                            .max(schema.min_length.unwrap_or(0))
+                            .min(schema.max_length.unwrap_or(u64::MAX));
+                        random_chars(rng, LOWERCASE_ALPHANUMERIC, length as usize)
+                    }
+                }
+            }
+            None => format
+                .and_then(string_from_format)
+                .unwrap_or(default)
+                .to_string(),
+        };
+
+        fit_length(&value, schema.min_length, schema.max_length)
+    }
+
+    /// An integer within the schema's bounds that is a multiple of `multipleOf`, the default is
+    /// used if it already satisfies them.
+    pub fn integer_from_schema(&self, schema: &Schema, default: i64) -> i64 {
+        self.number_within_bounds(schema, default as f64, Some(1.0))
+            .round() as i64
+    }
+
+    /// A number within the schema's bounds that is a multiple of `multipleOf`, the default is
+    /// used if it already satisfies them.
+    pub fn number_from_schema(&self, schema: &Schema, default: f64) -> f64 {
+        let value = self.number_within_bounds(schema, default, None);
+        match (&self.rng, &schema.multiple_of) {
+            // Keep random numbers readable
+            (Some(_), None) => (value * 100.0).round() / 100.0,
+            _ => value,
+        }
+    }
+
+    pub fn boolean(&self, default: bool) -> bool {
+        match &self.rng {
+            Some(rng) => rng.borrow_mut().bool(),
+            None => default,
+        }
+    }
+
+    /// The number of items to generate for an array, at least one unless `maxItems` forbids it.
+    /// With `uniqueItems` the items have to be varied to reach it, so there are at most
+    /// `distinct_items` if the items can only have that many values, e.g. an `enum`.
+    pub fn item_count(&self, schema: &Schema, distinct_items: Option<usize>) -> usize {
+        let min = schema.min_items.unwrap_or(1).max(1);
+        let count = match &self.rng {
+            Some(rng) => rng.borrow_mut().u64(min..=min + 2),
+            None => min,
+        };
+        let count = match schema.max_items {
+            Some(max) => count.min(max) as usize,
+            None => count as usize,
+        };
+
+        match distinct_items {
+            Some(distinct) if schema.unique_items.unwrap_or(false) => count.min(distinct),
+            _ => count,
+        }
+    }
+
+    /// `step` is how far inside an exclusive bound the value is placed. Without one it's half the
+    /// distance between the bounds, at most `NUMBER_STEP`, so the value fits between them.
+    fn number_within_bounds(&self, schema: &Schema, default: f64, step: Option<f64>) -> f64 {
+        let as_f64 = |n: &Option<serde_json::Number>| n.as_ref().and_then(|n| n.as_f64());
+        let multiple_of = as_f64(&schema.multiple_of).filter(|m| *m > 0.0);
+        let bounds_distance = match (
+            as_f64(&schema.minimum).or(as_f64(&schema.exclusive_minimum)),
+            as_f64(&schema.maximum).or(as_f64(&schema.exclusive_maximum)),
+        ) {
+            (Some(lower), Some(upper)) if upper > lower => Some(upper - lower),
+            _ => None,
+        };
+        let step = multiple_of.or(step).unwrap_or_else(|| {
+            bounds_distance.map_or(NUMBER_STEP, |distance| (distance / 2.0).min(NUMBER_STEP))
+        });
+
+        let lower = match (as_f64(&schema.minimum), as_f64(&schema.exclusive_minimum)) {
+            (Some(min), Some(exclusive_min)) => Some(min.max(exclusive_min + step)),
+            (Some(min), None) => Some(min),
+            (None, Some(exclusive_min)) => Some(exclusive_min + step),
+            (None, None) => None,
+        };
+        let upper = match (as_f64(&schema.maximum), as_f64(&schema.exclusive_maximum)) {
+            (Some(max), Some(exclusive_max)) => Some(max.min(exclusive_max - step)),
+            (Some(max), None) => Some(max),
+            (None, Some(exclusive_max)) => Some(exclusive_max - step),
+            (None, None) => None,
+        };
+        let upper = match (lower, upper) {
+            (Some(lower), Some(upper)) if lower > upper => {
+                warn!(
+                    "No number is within the bounds of the schema, using the lower bound {lower}"
+                );
+                None
+            }
+            _ => upper,
+        };
+
+        let mut value = match &self.rng {
+            Some(rng) => {
+                let start = lower.unwrap_or(upper.map_or(0.0, |u| u.min(0.0)));
+                let end = upper.unwrap_or(start + RANDOM_NUMBER_RANGE);
+                start + rng.borrow_mut().f64() * (end - start)
+            }
+            None => default,
+        };
+        if let Some(lower) = lower {
+            value = value.max(lower);
+        }
+        if let Some(upper) = upper {
+            value = value.min(upper);
+        }
+
+        if let Some(multiple_of) = multiple_of {
+            let multiple = (value / multiple_of).ceil() * multiple_of;
+            value = match upper {
+                Some(upper) if multiple > upper => (value / multiple_of).floor() * multiple_of,
+                _ => multiple,
+            };
+
+            // Multiplying leaves artifacts like 0.30000000000000004 for a multiple of 0.1
+            let precision = schema.multiple_of.as_ref().map_or(0, decimals);
+            let factor = 10f64.powi(precision);
+            value = (value * factor).round() / factor;
+        }
+
+        value
+    }
+}
+
+/// The number of decimals of the number, e.g. 2 for `0.25` or 5 for `1e-5`.
+fn decimals(number: &serde_json::Number) -> i32 {
+    let number = number.to_string();
+    let (mantissa, exponent) = number.split_once(['e', 'E']).unwrap_or((&number, "0"));
+    let fraction = mantissa.split_once('.').map_or(0, |(_, f)| f.len() as i32);
+
+    (fraction - exponent.parse::<i32>().unwrap_or(0)).max(0)
+}
+
+/// A value declared by the schema. A `const` is the only valid value, otherwise in order of
+/// precedence the `example`, the first of the `examples`, the `default` or the first `enum`
+/// value. `const` and enum values are read from the raw specification since `oas3` drops
+/// `const` and only keeps enum values as strings.
+pub fn declared_value(schema: &Schema, raw: RawNode) -> Option<serde_json::Value> {
+    raw.get("const")
+        .cloned()
+        .or_else(|| schema.example.clone())
+        .or_else(|| {
+            raw.get("examples")
+                .and_then(|examples| examples.as_array())
+                .and_then(|examples| examples.first())
+                .cloned()
+        })
+        .or_else(|| schema.default.clone())
+        .or_else(|| {
+            raw.get("enum")
+                .and_then(|values| values.as_array())
+                .and_then(|values| values.first())
+                .cloned()
+        })
+        .or_else(|| {
+            schema
+                .enum_values
+                .first()
+                .map(|e| serde_json::Value::String(e.to_string()))
+        })
+}
+
+fn fit_length(value: &str, min_length: Option<u64>, max_length: Option<u64>) -> String {
+    let mut chars: Vec<char> = value.chars().collect();
+    let min_length = min_length.unwrap_or(0) as usize;
+
+    if chars.len() < min_length {
+        let padding = match value.is_empty() {
+            true => vec!['a'],
+            false => chars.clone(),
+        };
+        chars = padding.into_iter().cycle().take(min_length).collect();
+    }
+
+    if let Some(max_length) = max_length {
+        chars.truncate(max_length as usize);
+    }
+
+    chars.into_iter().collect()
+}
+
+/// Sample values for the formats defined by JSON Schema and the OpenAPI Specification.
+/// See https://spec.openapis.org/oas/v3.1.0#data-types
+fn string_from_format(format: &str) -> Option<&'static str> {
+    match format {
+        "uuid" => Some("3fa85f64-5717-4562-b3fc-2c963f66afa6"),
+        "date-time" => Some("2024-01-01T12:00:00Z"),
+        "date" => Some("2024-01-01"),
+        "time" => Some("12:00:00Z"),
+        "email" => Some("user@example.com"),
+        "uri" | "url" => Some("https://example.com"),
+        "ipv4" => Some("192.0.2.1"),
+        "ipv6" => Some("2001:db8::1"),
+        "hostname" => Some("example.com"),
+        // base64 encoded "string"
+        "byte" => Some("c3RyaW5n"),
+        _ => None,
+    }
+}
+
+fn random_string_from_format(format: &str, rng: &mut Rng) -> Option<String> {
+    let date = |rng: &mut Rng| {
+        format!(
+            "{}-{:02}-{:02}",
+            rng.u32(2000..=2030),
+            rng.u32(1..=12),
+            rng.u32(1..=28)
+        )
+    };
+    let time = |rng: &mut Rng| {
+        format!(
+            "{:02}:{:02}:{:02}Z",
+            rng.u32(0..24),
+            rng.u32(0..60),
+            rng.u32(0..60)
+        )
+    };
+
+    match format {
+        "uuid" => Some(random_uuid(rng)),
+        "date-time" => Some(format!("{}T{}", date(rng), time(rng))),
+        "date" => Some(date(rng)),
+        "time" => Some(time(rng)),
+        "email" => Some(format!(
+            "user-{}@example.com",
+            random_chars(rng, LOWERCASE_ALPHANUMERIC, 8)
+        )),
+        "uri" | "url" => Some(format!(
+            "https://example.com/{}",
+            random_chars(rng, LOWERCASE_ALPHANUMERIC, 8)
+        )),
+        "ipv4" => Some(format!("192.0.2.{}", rng.u8(1..=254))),
+        "ipv6" => Some(format!("2001:db8::{:x}", rng.u16(1..))),
+        "hostname" => Some(format!(
+            "{}.example.com",
+            random_chars(rng, LOWERCASE_ALPHANUMERIC, 8)
+        )),
+        // Any four characters of the alphabet are valid base64
+        "byte" => Some(random_chars(rng, BASE64, 12)),
+        _ => None,
+    }
+}
+
+/// A version 4 UUID, see https://www.rfc-editor.org/rfc/rfc9562#name-uuid-version-4
+fn random_uuid(rng: &mut Rng) -> String {
+    let mut bytes: Vec<u8> = (0..16).map(|_| rng.u8(..)).collect();
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+fn random_chars(rng: &mut Rng, alphabet: &[u8], length: usize) -> String {
+    (0..length)
+        .map(|_| alphabet[rng.usize(..alphabet.len())] as char)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use oas3::Schema;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    use super::{declared_value, SampleValues};
+    use crate::raw_spec::RawSpec;
+
+    fn schema_with_format(format: &str) -> Schema {
+        Schema {
+            format: Some(format.to_string()),
+            ..Schema::default()
+        }
+    }
+
+    #[test]
+    fn string_from_schema_satisfies_format() {
+        assert_eq!(
+            "3fa85f64-5717-4562-b3fc-2c963f66afa6",
+            SampleValues::default().string_from_schema(&schema_with_format("uuid"), "string")
+        );
+        assert_eq!(
+            "2024-01-01T12:00:00Z",
+            SampleValues::default().string_from_schema(&schema_with_format("date-time"), "string")
+        );
+        assert_eq!(
+            "user@example.com",
+            SampleValues::default().string_from_schema(&schema_with_format("email"), "string")
+        );
+        assert_eq!(
+            "c3RyaW5n",
+            SampleValues::default().string_from_schema(&schema_with_format("byte"), "string")
+        );
+    }
+
+    #[test]
+    fn string_from_schema_defaults_without_known_format() {
+        assert_eq!(
+            "string",
+            SampleValues::default().string_from_schema(&Schema::default(), "string")
+        );
+        assert_eq!(
+            "string_value",
+            SampleValues::default()
+                .string_from_schema(&schema_with_format("password"), "string_value")
+        );
+    }
+
+    #[test]
+    fn string_from_schema_fits_length() {
+        let schema = Schema {
+            min_length: Some(10),
+            ..Schema::default()
+        };
+        assert_eq!(
+            "stringstri",
+            SampleValues::default().string_from_schema(&schema, "string")
+        );
+
+        let schema = Schema {
+            max_length: Some(3),
+            ..Schema::default()
+        };
+        assert_eq!(
+            "str",
+            SampleValues::default().string_from_schema(&schema, "string")
+        );
+    }
+
+    #[test]
+    fn integer_from_schema_satisfies_bounds_and_multiple_of() {
+        let number = |n: i64| Some(serde_json::Number::from(n));
+
+        let schema = Schema {
+            minimum: number(10),
+            ..Schema::default()
+        };
+        assert_eq!(10, SampleValues::default().integer_from_schema(&schema, 3));
+
+        let schema = Schema {
+            exclusive_maximum: number(0),
+            ..Schema::default()
+        };
+        assert_eq!(-1, SampleValues::default().integer_from_schema(&schema, 3));
+
+        let schema = Schema {
+            minimum: number(4),
+            maximum: number(20),
+            multiple_of: number(7),
+            ..Schema::default()
+        };
+        assert_eq!(7, SampleValues::default().integer_from_schema(&schema, 3));
+
+        assert_eq!(
+            3,
+            SampleValues::default().integer_from_schema(&Schema::default(), 3)
+        );
+    }
+
+    #[test]
+    fn number_from_schema_satisfies_bounds_and_multiple_of() {
+        let schema = Schema {
+            exclusive_minimum: serde_json::Number::from_f64(5.0),
+            multiple_of: serde_json::Number::from_f64(0.25),
+            ..Schema::default()
+        };
+        assert_eq!(
+            5.25,
+            SampleValues::default().number_from_schema(&schema, 3.3)
+        );
+
+        let schema = Schema {
+            minimum: serde_json::Number::from_f64(0.3),
+            multiple_of: serde_json::Number::from_f64(0.1),
+            ..Schema::default()
+        };
+        assert_eq!(
+            0.3,
+            SampleValues::default().number_from_schema(&schema, 0.0)
+        );
+
+        let random_values = SampleValues::random(3);
+        for _ in 0..20 {
+            let value = random_values.number_from_schema(&schema, 0.0);
+            assert_eq!(
+                value,
+                (value * 10.0).round() / 10.0,
+                "{value} has artifacts"
+            );
+        }
+
+        let schema = Schema {
+            minimum: serde_json::Number::from_f64(0.000015),
+            multiple_of: serde_json::Number::from_f64(1e-5),
+            ..Schema::default()
+        };
+        assert_eq!(
+            0.00002,
+            SampleValues::default().number_from_schema(&schema, 0.0)
+        );
+    }
+
+    #[test]
+    fn number_from_schema_fits_between_close_bounds() {
+        let number = |n: f64| serde_json::Number::from_f64(n);
+
+        let schema = Schema {
+            minimum: number(1.2),
+            exclusive_maximum: number(1.5),
+            ..Schema::default()
+        };
+        assert_eq!(
+            1.35,
+            SampleValues::default().number_from_schema(&schema, 5.5)
+        );
+
+        let schema = Schema {
+            exclusive_minimum: number(0.0),
+            exclusive_maximum: number(0.2),
+            ..Schema::default()
+        };
+        assert_eq!(
+            0.1,
+            SampleValues::default().number_from_schema(&schema, 5.5)
+        );
+
+        let random_values = SampleValues::random(3);
+        for _ in 0..20 {
+            let value = random_values.number_from_schema(&schema, 5.5);
+            assert!(0.0 < value && value < 0.2, "{value} isn't within (0, 0.2)");
+        }
+    }
+
+    #[test]
+    fn number_from_schema_prefers_the_lower_bound_if_bounds_exclude_each_other() {
+        let schema = Schema {
+            minimum: serde_json::Number::from_f64(10.0),
+            maximum: serde_json::Number::from_f64(2.0),
+            ..Schema::default()
+        };
+        assert_eq!(
+            10.0,
+            SampleValues::default().number_from_schema(&schema, 5.5)
+        );
+        assert_eq!(10, SampleValues::default().integer_from_schema(&schema, 3));
+    }
+
+    #[test]
+    fn item_count_satisfies_min_and_max_items() {
+        let schema = Schema {
+            min_items: Some(2),
+            ..Schema::default()
+        };
+        assert_eq!(2, SampleValues::default().item_count(&schema, None));
+
+        let schema = Schema {
+            max_items: Some(0),
+            ..Schema::default()
+        };
+        assert_eq!(0, SampleValues::default().item_count(&schema, None));
+
+        assert_eq!(
+            1,
+            SampleValues::default().item_count(&Schema::default(), None)
+        );
+    }
+
+    #[test]
+    fn item_count_of_unique_items_is_at_most_the_distinct_items() {
+        let schema = Schema {
+            min_items: Some(3),
+            unique_items: Some(true),
+            ..Schema::default()
+        };
+        for seed in 0..20 {
+            assert_eq!(3, SampleValues::random(seed).item_count(&schema, Some(3)));
+        }
+        assert_eq!(2, SampleValues::default().item_count(&schema, Some(2)));
+
+        let schema = Schema {
+            unique_items: None,
+            ..schema
+        };
+        assert_eq!(3, SampleValues::default().item_count(&schema, Some(2)));
+    }
+
+    #[test]
+    fn random_values_are_reproducible_and_satisfy_the_schema() {
+        let schema = Schema {
+            format: Some("uuid".to_string()),
+            ..Schema::default()
+        };
+        let uuid = SampleValues::random(7).string_from_schema(&schema, "string");
+
+        assert_eq!(
+            uuid,
+            SampleValues::random(7).string_from_schema(&schema, "string")
+        );
+        assert_ne!(
+            uuid,
+            SampleValues::random(8).string_from_schema(&schema, "string")
+        );
+        assert_eq!(36, uuid.len());
+        assert_eq!(Some('4'), uuid.chars().nth(14));
+
+        let schema = Schema {
+            minimum: Some(serde_json::Number::from(10)),
+            maximum: Some(serde_json::Number::from(20)),
+            multiple_of: Some(serde_json::Number::from(5)),
+            ..Schema::default()
+        };
+        let values = SampleValues::random(7);
+        for _ in 0..20 {
+            assert!([10, 15, 20].contains(&values.integer_from_schema(&schema, 3)));
+        }
+    }
+
+    #[test]
+    fn dynamic_string_uses_hurl_generators() {
+        let values = SampleValues::default().with_dynamic_values(true);
+
+        assert_eq!(
+            "{{newUuid}}",
+            values.string_from_schema(&schema_with_format("uuid"), "string")
+        );
+        assert_eq!(
+            "user-{{newUuid}}@example.com",
+            values.string_from_schema(&schema_with_format("email"), "string")
+        );
+        assert_eq!(
+            "2024-01-01",
+            values.string_from_schema(&schema_with_format("date"), "string")
+        );
+
+        let too_short = Schema {
+            max_length: Some(20),
+            ..schema_with_format("email")
+        };
+        assert_eq!(None, values.dynamic_string(&too_short));
+        assert_eq!(
+            None,
+            SampleValues::default().dynamic_string(&schema_with_format("uuid"))
+        );
+    }
+
+    #[test]
+    fn declared_value_precedence() {
+        let raw = RawSpec::from_str(r#"{"examples": ["from examples"]}"#).unwrap();
+        let schema = Schema {
+            default: Some(json!("from default")),
+            enum_values: vec!["from enum".to_string()],
+            ..Schema::default()
+        };
+
+        assert_eq!(
+            Some(json!("from example")),
+            declared_value(
+                &Schema {
+                    example: Some(json!("from example")),
+                    ..schema.clone()
+                },
+                raw.root()
+            )
+        );
+        assert_eq!(
+            Some(json!("from examples")),
+            declared_value(&schema, raw.root())
+        );
+
+        let no_examples = RawSpec::default();
+        assert_eq!(
+            Some(json!("from default")),
+            declared_value(&schema, no_examples.root())
+        );
+        assert_eq!(
+            Some(json!("from enum")),
+            declared_value(
+                &Schema {
+                    default: None,
+                    ..schema
+                },
+                no_examples.root()
+            )
+        );
+        assert_eq!(None, declared_value(&Schema::default(), no_examples.root()));
+
+        let constant = RawSpec::from_str(r#"{"const": 3, "examples": [4]}"#).unwrap();
+        assert_eq!(
+            Some(json!(3)),
+            declared_value(&Schema::default(), constant.root())
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use oas3::{spec::ObjectOrReference, Schema};
+
+/// How deeply schemas are nested before a walk through them stops, if no maximum is configured.
+pub const DEFAULT_MAX_DEPTH: usize = 10;
+
+/// The schemas a walk went through to reach the current schema. Recursive schemas reference one
+/// of their ancestors, a walk stops at those and at the maximum depth so it can't loop forever.
+#[derive(Clone, Copy)]
+pub struct SchemaAncestors<'a> {
+    parent: Option<&'a SchemaAncestors<'a>>,
+    ref_path: Option<&'a str>,
+    depth: usize,
+    max_depth: usize,
+}
+
+impl<'a> SchemaAncestors<'a> {
+    pub fn root(max_depth: usize) -> Self {
+        Self {
+            parent: None,
+            ref_path: None,
+            depth: 0,
+            max_depth,
+        }
+    }
+
+    /// The ancestors of a schema nested in the current one.
+    pub fn child(&'a self, schema: &'a ObjectOrReference<Schema>) -> Self {
+        Self {
+            parent: Some(self),
+            ref_path: ref_path(schema),
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+        }
+    }
+
+    /// The ancestors of a nested schema that can't be a reference, e.g. one `oas3` drops.
+    pub fn inline_child(&'a self) -> Self {
+        Self {
+            parent: Some(self),
+            ref_path: None,
+            depth: self.depth + 1,
+            max_depth: self.max_depth,
+        }
+    }
+
+    /// Whether the walk should stop before a schema nested in the current one, because it
+    /// references an ancestor or it's nested too deeply.
+    pub fn stops_at(&self, schema: &ObjectOrReference<Schema>) -> bool {
+        self.is_at_max_depth() || ref_path(schema).is_some_and(|r| self.references(r))
+    }
+
+    pub fn is_at_max_depth(&self) -> bool {
+        self.depth >= self.max_depth
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
+    fn references(&self, ref_path: &str) -> bool {
+        self.ref_path == Some(ref_path) || self.parent.is_some_and(|p| p.references(ref_path))
+    }
+}
+
+fn ref_path(schema: &ObjectOrReference<Schema>) -> Option<&str> {
+    match schema {
+        ObjectOrReference::Ref { ref_path } => Some(ref_path),
+        ObjectOrReference::Object(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oas3::{spec::ObjectOrReference, Schema};
+    use pretty_assertions::assert_eq;
+
+    use super::SchemaAncestors;
+
+    fn reference(name: &str) -> ObjectOrReference<Schema> {
+        ObjectOrReference::Ref {
+            ref_path: format!("#/components/schemas/{name}"),
+        }
+    }
+
+    #[test]
+    fn stops_at_references_to_ancestors() {
+        let (node, tag) = (reference("Node"), reference("Tag"));
+        let inline = ObjectOrReference::Object(Schema::default());
+        let root = SchemaAncestors::root(10);
+        let in_node = root.child(&node);
+        let in_inline = in_node.child(&inline);
+
+        assert_eq!(false, root.stops_at(&node));
+        assert_eq!(true, in_node.stops_at(&node));
+        assert_eq!(true, in_inline.stops_at(&node));
+        assert_eq!(false, in_inline.stops_at(&tag));
+    }
+
+    #[test]
+    fn stops_at_max_depth() {
+        let tag = reference("Tag");
+        let root = SchemaAncestors::root(2);
+        let first = root.inline_child();
+        let second = first.inline_child();
+
+        assert_eq!(false, first.stops_at(&tag));
+        assert_eq!(true, second.stops_at(&tag));
+    }
+}
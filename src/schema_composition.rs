@@ -0,0 +1,185 @@
+use log::debug;
+use oas3::{
+    spec::{ObjectOrReference, RefError},
+    Schema, Spec,
+};
+use serde_json::Number;
+
+/// Merges the `allOf` subschemas of a schema into a single schema a value has to match instead.
+/// Subschemas are merged recursively and each reference is resolved once, so a subschema
+/// referencing one of the schemas it's merged into is skipped.
+///
+/// Properties and `required` lists are combined, with properties of the schema itself taking
+/// precedence over inherited ones. Where subschemas constrain the same keyword the strictest
+/// constraint is kept, e.g. the largest `minimum`. Other keywords are taken from the schema
+/// itself, or from the last subschema that declares them.
+pub fn merge_all_of(schema: Schema, spec: &Spec) -> Result<Schema, RefError> {
+    merge_subschemas(schema, spec, &mut vec![])
+}
+
+fn merge_subschemas(
+    mut schema: Schema,
+    spec: &Spec,
+    merged_refs: &mut Vec<String>,
+) -> Result<Schema, RefError> {
+    let mut merged = Schema::default();
+
+    for subschema in std::mem::take(&mut schema.all_of) {
+        if let ObjectOrReference::Ref { ref_path } = &subschema {
+            if merged_refs.contains(ref_path) {
+                debug!("Not merging {ref_path} into allOf again, it's already merged or recursive");
+                continue;
+            }
+            merged_refs.push(ref_path.to_string());
+        }
+
+        let resolved = merge_subschemas(subschema.resolve(spec)?, spec, merged_refs)?;
+        merged = merge(merged, resolved);
+    }
+
+    Ok(merge(merged, schema))
+}
+
+/// Merges `schema` into `base`, keywords of `schema` take precedence unless they're constraints.
+fn merge(base: Schema, schema: Schema) -> Schema {
+    let mut required = base.required;
+    for name in schema.required {
+        if !required.contains(&name) {
+            required.push(name);
+        }
+    }
+
+    let mut properties = base.properties;
+    properties.extend(schema.properties);
+
+    Schema {
+        title: schema.title.or(base.title),
+        description: schema.description.or(base.description),
+        schema_type: schema.schema_type.or(base.schema_type),
+        required,
+        items: schema.items.or(base.items),
+        properties,
+        additional_properties: schema.additional_properties.or(base.additional_properties),
+        default: schema.default.or(base.default),
+        example: schema.example.or(base.example),
+        format: schema.format.or(base.format),
+        enum_values: non_empty(schema.enum_values, base.enum_values),
+        pattern: schema.pattern.or(base.pattern),
+        multiple_of: schema.multiple_of.or(base.multiple_of),
+        minimum: strictest(schema.minimum, base.minimum, larger_number),
+        exclusive_maximum: strictest(
+            schema.exclusive_maximum,
+            base.exclusive_maximum,
+            smaller_number,
+        ),
+        maximum: strictest(schema.maximum, base.maximum, smaller_number),
+        exclusive_minimum: strictest(
+            schema.exclusive_minimum,
+            base.exclusive_minimum,
+            larger_number,
+        ),
+        min_length: strictest(schema.min_length, base.min_length, u64::max),
+        max_length: strictest(schema.max_length, base.max_length, u64::min),
+        min_items: strictest(schema.min_items, base.min_items, u64::max),
+        max_items: strictest(schema.max_items, base.max_items, u64::min),
+        unique_items: strictest(schema.unique_items, base.unique_items, |a, b| a || b),
+        max_properties: strictest(schema.max_properties, base.max_properties, u64::min),
+        min_properties: strictest(schema.min_properties, base.min_properties, u64::max),
+        read_only: strictest(schema.read_only, base.read_only, |a, b| a || b),
+        write_only: strictest(schema.write_only, base.write_only, |a, b| a || b),
+        all_of: vec![],
+        one_of: non_empty(schema.one_of, base.one_of),
+        any_of: non_empty(schema.any_of, base.any_of),
+    }
+}
+
+fn strictest<T>(a: Option<T>, b: Option<T>, pick: impl Fn(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(pick(a, b)),
+        (a, b) => a.or(b),
+    }
+}
+
+fn non_empty<T>(values: Vec<T>, fallback: Vec<T>) -> Vec<T> {
+    match values.is_empty() {
+        true => fallback,
+        false => values,
+    }
+}
+
+fn larger_number(a: Number, b: Number) -> Number {
+    match b.as_f64() > a.as_f64() {
+        true => b,
+        false => a,
+    }
+}
+
+fn smaller_number(a: Number, b: Number) -> Number {
+    match b.as_f64() < a.as_f64() {
+        true => b,
+        false => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use oas3::Schema;
+    use pretty_assertions::assert_eq;
+
+    use super::merge_all_of;
+
+    #[test]
+    fn merge_all_of_combines_nested_subschemas() {
+        let spec = oas3::from_str(
+            r##"{
+                "openapi": "3.1.0",
+                "info": {"title": "Test", "version": "1.0.0"},
+                "paths": {},
+                "components": {"schemas": {
+                    "Named": {
+                        "type": "object",
+                        "required": ["name"],
+                        "properties": {"name": {"type": "string"}},
+                        "allOf": [{"$ref": "#/components/schemas/Named"}]
+                    },
+                    "Pet": {
+                        "allOf": [
+                            {"$ref": "#/components/schemas/Named"},
+                            {"required": ["age"], "properties": {"age": {"type": "integer"}}, "minProperties": 1}
+                        ],
+                        "minProperties": 2,
+                        "maxProperties": 5
+                    }
+                }}
+            }"##,
+        )
+        .unwrap();
+        let pet: Schema = serde_json::from_str(
+            r##"{
+                "allOf": [
+                    {"$ref": "#/components/schemas/Pet"},
+                    {"required": ["name", "tag"], "properties": {"tag": {"type": "string"}}}
+                ],
+                "maxProperties": 3
+            }"##,
+        )
+        .unwrap();
+
+        let merged = merge_all_of(pet, &spec).unwrap();
+
+        assert_eq!(true, merged.all_of.is_empty());
+        assert_eq!(
+            Some(oas3::spec::SchemaTypeSet::Single(
+                oas3::spec::SchemaType::Object
+            )),
+            merged.schema_type
+        );
+        assert_eq!(vec!["name", "age", "tag"], merged.required);
+        assert_eq!(
+            vec!["age", "name", "tag"],
+            merged.properties.keys().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(2), merged.min_properties);
+        assert_eq!(Some(3), merged.max_properties);
+    }
+}
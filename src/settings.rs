@@ -1,30 +1,85 @@
 use crate::{
     cli::{
-        Cli, ErrorHandling, Formatting, LogLevel, PathParamChoice, QueryParamChoice,
-        ResponseValidationChoice, VariablesUpdateStrategy,
+        BodyFieldChoice, Cli, DataChoice, ErrorHandling, Formatting, LogLevel, NullValueChoice,
+        PathParamChoice, QueryParamChoice, RequestMediaTypeChoice, ResponseValidationChoice,
+        UnionVariantChoice, VariablesUpdateStrategy,
     },
     content_type::ContentType,
+    sample_values::values::SampleValues,
     variable_files::CustomVariables,
 };
 
-#[derive(Default)]
+/// Which documented response of an operation is validated.
+#[derive(Clone, Default)]
+pub enum ResponseCodeChoice {
+    /// The documented success response.
+    #[default]
+    Success,
+    /// The response documented under this status code, range or key, e.g. `404` or `default`.
+    Code(String),
+    /// One entry for every documented status code.
+    All,
+}
+
 pub struct Settings {
     pub input: Option<std::path::PathBuf>,
     pub out_dir: Option<std::path::PathBuf>,
     pub validate_response: ResponseValidationChoice,
+    pub response_code: ResponseCodeChoice,
     pub query_params_choice: QueryParamChoice,
+    pub body_fields: BodyFieldChoice,
+    pub enum_property: Option<String>,
+    pub null_values: NullValueChoice,
     pub path_params_choice: PathParamChoice,
+    pub sample_values: SampleValues,
     pub custom_variables: CustomVariables,
     pub variables_update_strategy: VariablesUpdateStrategy,
     pub operation_id_selection: Option<Vec<String>>,
     pub tags: Option<Vec<String>>,
     pub formatting: Formatting,
     pub content_type: ContentType,
+    pub media_types: Vec<String>,
+    pub request_media_types: RequestMediaTypeChoice,
+    pub media_type_headers: bool,
+    pub union_variants: UnionVariantChoice,
+    pub max_depth: Option<usize>,
     pub log_level: LogLevel,
     pub quiet: bool,
     pub error_handling: ErrorHandling,
 }
 
+/// The settings of the CLI when no options are passed.
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            input: None,
+            out_dir: None,
+            validate_response: ResponseValidationChoice::default(),
+            response_code: ResponseCodeChoice::default(),
+            query_params_choice: QueryParamChoice::default(),
+            body_fields: BodyFieldChoice::default(),
+            enum_property: None,
+            null_values: NullValueChoice::default(),
+            path_params_choice: PathParamChoice::default(),
+            sample_values: SampleValues::default(),
+            custom_variables: CustomVariables::default(),
+            variables_update_strategy: VariablesUpdateStrategy::default(),
+            operation_id_selection: None,
+            tags: None,
+            formatting: Formatting::default(),
+            content_type: ContentType::default(),
+            media_types: vec![],
+            request_media_types: RequestMediaTypeChoice::default(),
+            media_type_headers: true,
+            union_variants: UnionVariantChoice::default(),
+            max_depth: None,
+            log_level: LogLevel::default(),
+            quiet: false,
+            error_handling: ErrorHandling::default(),
+        }
+    }
+}
+
 impl TryFrom<Cli> for Settings {
     type Error = anyhow::Error;
 
@@ -33,8 +88,23 @@ impl TryFrom<Cli> for Settings {
             input: cli.input,
             out_dir: cli.out_dir,
             validate_response: cli.validation,
+            response_code: match cli.response_code.as_deref() {
+                None => ResponseCodeChoice::Success,
+                Some("all") => ResponseCodeChoice::All,
+                Some(code) => ResponseCodeChoice::Code(code.to_string()),
+            },
             query_params_choice: cli.query_params,
+            body_fields: cli.body_fields,
+            enum_property: cli.enum_property,
+            null_values: cli.null_values,
             path_params_choice: cli.path_params,
+            sample_values: match cli.data {
+                DataChoice::Fixed => SampleValues::default(),
+                DataChoice::Random => {
+                    SampleValues::random(cli.seed.unwrap_or_else(|| fastrand::u64(..)))
+                }
+            }
+            .with_dynamic_values(cli.dynamic_values),
             variables_update_strategy: cli.variables_file_update,
             custom_variables: CustomVariables {
                 headers: cli.header_vars,
@@ -43,6 +113,11 @@ impl TryFrom<Cli> for Settings {
             tags: cli.tag,
             formatting: cli.formatting,
             content_type: cli.content_type.into(),
+            media_types: cli.media_type,
+            request_media_types: cli.request_media_types,
+            media_type_headers: !cli.no_media_type_headers,
+            union_variants: cli.union_variants,
+            max_depth: cli.max_depth,
             log_level: cli.log_level,
             quiet: cli.quiet,
             error_handling: cli.error_handling,
@@ -4,39 +4,64 @@ use std::io::Read;
 use anyhow::{Context, Result};
 use oas3::Spec;
 
+use crate::raw_spec::{make_oas3_compatible, RawSpec};
+
 const GENERIC_ERROR_MESSAGE: &str = "Invalid Open API 3.1 Specification or file I/O error.";
 const NOT_MATCHED_UNTAGGED_ENUM_MSG: &str =
     "data did not match any variant of untagged enum ObjectOrReference";
 
-pub fn from_path(p: std::path::PathBuf) -> Result<Spec, anyhow::Error> {
-    match oas3::from_path(p.clone()).with_context(|| GENERIC_ERROR_MESSAGE) {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            let error_message = e.root_cause().to_string();
-            if error_message.contains(NOT_MATCHED_UNTAGGED_ENUM_MSG) {
-                Err(anyhow!(format!("Specification error at: {}. Please ensure the object at that location has the correct structure and all types match OpenAPI Specification v3.1.0, earlier or later versions are not supported by this tool.", error_message.replace(": data did not match any variant of untagged enum ObjectOrReference at", "")))).with_context(|| GENERIC_ERROR_MESSAGE)
-            } else {
-                Err(e)
-            }
-        }
-    }
+/// The parsed specification along with the document it was parsed from.
+#[derive(Debug)]
+pub struct SpecDocument {
+    pub spec: Spec,
+    pub raw: RawSpec,
 }
 
-pub fn from_reader<R>(p: R) -> Result<Spec, anyhow::Error>
+pub fn from_path(p: std::path::PathBuf) -> Result<SpecDocument, anyhow::Error> {
+    let contents = std::fs::read_to_string(p).with_context(|| GENERIC_ERROR_MESSAGE)?;
+
+    from_str(&contents)
+}
+
+pub fn from_reader<R>(mut p: R) -> Result<SpecDocument, anyhow::Error>
 where
     R: Read,
 {
-    match oas3::from_reader(p).with_context(|| GENERIC_ERROR_MESSAGE) {
-        Ok(s) => Ok(s),
-        Err(e) => {
-            let error_message = e.root_cause().to_string();
-            if error_message.contains(NOT_MATCHED_UNTAGGED_ENUM_MSG) {
-                Err(anyhow!(format!("Specification error at: {}. Please ensure the object at that location has the correct structure and all types match OpenAPI Specification v3.1.0, earlier or later versions are not supported by this tool.", error_message.replace(": data did not match any variant of untagged enum ObjectOrReference at", "")))).with_context(|| GENERIC_ERROR_MESSAGE)
-            } else {
-                Err(e)
+    let mut contents = String::new();
+    p.read_to_string(&mut contents)
+        .with_context(|| GENERIC_ERROR_MESSAGE)?;
+
+    from_str(&contents)
+}
+
+fn from_str(contents: &str) -> Result<SpecDocument, anyhow::Error> {
+    let spec = match oas3::from_str(contents).with_context(|| GENERIC_ERROR_MESSAGE) {
+        Ok(s) => s,
+        Err(e) => match compatible_spec(contents) {
+            Some(s) => s,
+            None => {
+                let error_message = e.root_cause().to_string();
+                if error_message.contains(NOT_MATCHED_UNTAGGED_ENUM_MSG) {
+                    return Err(anyhow!(format!("Specification error at: {}. Please ensure the object at that location has the correct structure and all types match OpenAPI Specification v3.1.0, earlier or later versions are not supported by this tool.", error_message.replace(": data did not match any variant of untagged enum ObjectOrReference at", "")))).with_context(|| GENERIC_ERROR_MESSAGE);
+                } else {
+                    return Err(e);
+                }
             }
-        }
-    }
+        },
+    };
+
+    Ok(SpecDocument {
+        spec,
+        raw: RawSpec::from_str(contents).with_context(|| GENERIC_ERROR_MESSAGE)?,
+    })
+}
+
+/// Parses the specification after rewriting the valid keywords `oas3` can't parse.
+fn compatible_spec(contents: &str) -> Option<Spec> {
+    let mut document: serde_json::Value = serde_yml::from_str(contents).ok()?;
+    make_oas3_compatible(&mut document);
+
+    serde_json::from_value(document).ok()
 }
 
 #[cfg(test)]
@@ -127,7 +127,9 @@ mod tests {
     #[test]
     fn variables_file_from_spec() {
         let spec_path = PathBuf::from_str("test_files/pet_store.json").unwrap();
-        let spec = oas3::from_path(spec_path.clone()).unwrap();
+        let spec = crate::spec_reader::from_path(spec_path.clone())
+            .unwrap()
+            .spec;
         let expected = VariableFiles {
             files: vec![VariableFile {
                 name: "petstore.swagger.io_v1".to_string(),